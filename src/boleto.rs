@@ -0,0 +1,781 @@
+use crate::{CheckDigit, Decimal, DigitRule};
+use chrono::{Days, Local, NaiveDate};
+use std::{fmt, str::FromStr};
+
+/// Number of digits in a barcode.
+const BARCODE_LEN: usize = 44;
+
+/// General check digit of bank boletos: remainders 0, 1 and 10 give 1.
+const BOLETO_CHECK_DIGIT: CheckDigit =
+    CheckDigit::MOD11.with_rule(DigitRule::Complement { overflow: 1 });
+
+/// Base date of the due date factor: factor 1000 is 2000-07-03.
+const FATOR_BASE: NaiveDate = match NaiveDate::from_ymd_opt(1997, 10, 7) {
+    Some(date) => date,
+    None => panic!("invalid date"),
+};
+
+/// Factors go from 1000 to 9999 and restart at 1000 (first rollover on 2025-02-22).
+const FATOR_CICLO: i64 = 9000;
+
+/// Errors returned when parsing a boleto or a collection slip.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BoletoError {
+    /// The number of digits, after removing spaces, dots and hyphens, is invalid.
+    InvalidLength(usize),
+    InvalidCharacter(char),
+    /// Check digit of a field of the digitable line (fields numbered from 1).
+    InvalidFieldCheckDigit {
+        field: usize,
+        expected: u8,
+        found: u8,
+    },
+    /// General check digit of the barcode.
+    InvalidCheckDigit {
+        expected: u8,
+        found: u8,
+    },
+    /// Collection slips start with 8 and bank boletos do not.
+    InvalidProduct(char),
+    /// Value identifier of a collection slip: 6, 7, 8 or 9.
+    InvalidValueIdentifier(char),
+    /// Due date outside the range of the factor.
+    InvalidDueDate(NaiveDate),
+    /// Amount negative or with more than 10 digits.
+    InvalidAmount(Decimal),
+    /// Free field without 25 digits.
+    InvalidFreeField(String),
+}
+
+impl fmt::Display for BoletoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BoletoError::InvalidLength(len) => {
+                write!(f, "boleto must have 44, 47 or 48 digits, found {len}")
+            }
+            BoletoError::InvalidCharacter(ch) => write!(f, "invalid character in boleto: {ch:?}"),
+            BoletoError::InvalidFieldCheckDigit {
+                field,
+                expected,
+                found,
+            } => write!(
+                f,
+                "invalid check digit in field {field}: expected {expected}, found {found}"
+            ),
+            BoletoError::InvalidCheckDigit { expected, found } => write!(
+                f,
+                "invalid boleto check digit: expected {expected}, found {found}"
+            ),
+            BoletoError::InvalidProduct(ch) => write!(f, "invalid product identifier: {ch:?}"),
+            BoletoError::InvalidValueIdentifier(ch) => {
+                write!(f, "invalid value identifier: {ch:?}")
+            }
+            BoletoError::InvalidDueDate(date) => {
+                write!(f, "due date out of the factor range: {date}")
+            }
+            BoletoError::InvalidAmount(amount) => write!(f, "invalid boleto amount: {amount}"),
+            BoletoError::InvalidFreeField(field) => {
+                write!(f, "free field must have 25 digits: '{field}'")
+            }
+        }
+    }
+}
+
+impl std::error::Error for BoletoError {}
+
+/**
+Bank boleto (boleto bancário), FEBRABAN layout.
+
+Parsed from the 47-digit digitable line (linha digitável) or the 44-digit barcode.
+
+Barcode layout:
+- 1 to 3: bank code
+- 4: currency code (9 = real)
+- 5: general check digit (mod-11)
+- 6 to 9: due date factor
+- 10 to 19: amount in cents
+- 20 to 44: free field, defined by the bank
+
+Example:
+```
+    use claudiofsr_lib::{Boleto, Decimal};
+    use chrono::NaiveDate;
+
+    let boleto: Boleto = "00190.50095 40144.816069 06809.350314 3 37370000000100"
+        .parse()
+        .unwrap();
+
+    assert_eq!(boleto.codigo_de_barras(), "00193373700000001000500940144816060680935031");
+    assert_eq!(boleto.banco(), "001");
+    assert_eq!(boleto.valor(), Decimal::new(100, 2));
+    assert_eq!(boleto.fator_vencimento(), 3737);
+    assert_eq!(
+        boleto.vencimento_com_referencia(NaiveDate::from_ymd_opt(2007, 12, 1).unwrap()),
+        NaiveDate::from_ymd_opt(2007, 12, 31)
+    );
+    assert_eq!(boleto.campo_livre(), "0500940144816060680935031");
+    assert_eq!(boleto.linha_digitavel(), "00190500954014481606906809350314337370000000100");
+    assert_eq!(boleto.to_string(), "00190.50095 40144.816069 06809.350314 3 37370000000100");
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Boleto([u8; BARCODE_LEN]);
+
+impl Boleto {
+    /// Parse the digitable line or the barcode, with or without the mask.
+    pub fn parse(text: &str) -> Result<Self, BoletoError> {
+        let digits = normalize(text)?;
+
+        match digits.len() {
+            47 => Boleto::from_linha_digitavel(&digits),
+            BARCODE_LEN => Boleto::from_codigo_de_barras(&digits),
+            len => Err(BoletoError::InvalidLength(len)),
+        }
+    }
+
+    /// Parse the 47-digit digitable line, checking the field and general check digits.
+    pub fn from_linha_digitavel(linha: &str) -> Result<Self, BoletoError> {
+        let linha = normalize(linha)?;
+
+        if linha.len() != 47 {
+            return Err(BoletoError::InvalidLength(linha.len()));
+        }
+
+        for (field, range) in [(1, 0..10), (2, 10..21), (3, 21..32)] {
+            check_field(field, &linha[range], CheckDigit::MOD10)?;
+        }
+
+        let barcode = [
+            &linha[0..4],
+            &linha[32..33],
+            &linha[33..47],
+            &linha[4..9],
+            &linha[10..20],
+            &linha[21..31],
+        ]
+        .concat();
+
+        Boleto::from_codigo_de_barras(&barcode)
+    }
+
+    /// Parse the 44-digit barcode, checking the general check digit.
+    pub fn from_codigo_de_barras(barcode: &str) -> Result<Self, BoletoError> {
+        let barcode = normalize(barcode)?;
+
+        if barcode.len() != BARCODE_LEN {
+            return Err(BoletoError::InvalidLength(barcode.len()));
+        }
+
+        if barcode.starts_with('8') {
+            return Err(BoletoError::InvalidProduct('8'));
+        }
+
+        let expected = general_check_digit(&barcode, BOLETO_CHECK_DIGIT);
+        let found = barcode.as_bytes()[4] - b'0';
+
+        if expected != found {
+            return Err(BoletoError::InvalidCheckDigit { expected, found });
+        }
+
+        let mut bytes = [0; BARCODE_LEN];
+        bytes.copy_from_slice(barcode.as_bytes());
+        Ok(Boleto(bytes))
+    }
+
+    /**
+    Build a boleto with currency 9 (real), computing the check digit.
+
+    Without a due date, the factor is "0000".
+    ```
+        use claudiofsr_lib::{Boleto, Decimal};
+        use chrono::NaiveDate;
+
+        let vencimento = NaiveDate::from_ymd_opt(2025, 2, 22);
+        let boleto = Boleto::new("341", vencimento, Decimal::new(12345, 2), "1091234567812345678901230").unwrap();
+        assert_eq!(boleto.fator_vencimento(), 1000);
+        assert_eq!(boleto.valor().to_string(), "123.45");
+    ```
+    */
+    pub fn new(
+        banco: &str,
+        vencimento: Option<NaiveDate>,
+        valor: Decimal,
+        campo_livre: &str,
+    ) -> Result<Self, BoletoError> {
+        let banco = normalize(banco)?;
+        if banco.len() != 3 {
+            return Err(BoletoError::InvalidLength(banco.len()));
+        }
+
+        let campo_livre = normalize(campo_livre)?;
+        if campo_livre.len() != 25 {
+            return Err(BoletoError::InvalidFreeField(campo_livre));
+        }
+
+        let fator = match vencimento {
+            Some(date) => fator_vencimento(date).ok_or(BoletoError::InvalidDueDate(date))?,
+            None => 0,
+        };
+
+        let cents = valor.round(2);
+        let cents = match u64::try_from(cents.mantissa()) {
+            Ok(cents) if cents <= 9_999_999_999 => cents,
+            _ => return Err(BoletoError::InvalidAmount(valor)),
+        };
+
+        let mut barcode = format!("{banco}90{fator:04}{cents:010}{campo_livre}");
+        let digit = general_check_digit(&barcode, BOLETO_CHECK_DIGIT);
+        barcode.replace_range(4..5, &digit.to_string());
+
+        Boleto::from_codigo_de_barras(&barcode)
+    }
+
+    /// The 44 digits of the barcode.
+    pub fn codigo_de_barras(&self) -> &str {
+        // Only ASCII digits are stored.
+        std::str::from_utf8(&self.0).unwrap_or_default()
+    }
+
+    /// The 47 digits of the digitable line, without the mask.
+    pub fn linha_digitavel(&self) -> String {
+        let b = self.codigo_de_barras();
+
+        let campo1 = with_check_digit(&[&b[0..4], &b[19..24]].concat(), CheckDigit::MOD10);
+        let campo2 = with_check_digit(&b[24..34], CheckDigit::MOD10);
+        let campo3 = with_check_digit(&b[34..44], CheckDigit::MOD10);
+
+        [&campo1, &campo2, &campo3, &b[4..5], &b[5..19]].concat()
+    }
+
+    /// Bank code (código do banco), 3 digits.
+    pub fn banco(&self) -> &str {
+        &self.codigo_de_barras()[0..3]
+    }
+
+    /// Currency code: 9 for real.
+    pub fn moeda(&self) -> u8 {
+        self.0[3] - b'0'
+    }
+
+    /// General check digit, the 5th digit of the barcode.
+    pub fn digito_verificador(&self) -> u8 {
+        self.0[4] - b'0'
+    }
+
+    /// Due date factor (fator de vencimento), 0 if there is no due date.
+    pub fn fator_vencimento(&self) -> u16 {
+        self.codigo_de_barras()[5..9].parse().unwrap_or_default()
+    }
+
+    /**
+    Due date, in the factor cycle closest to the reference date.
+
+    The factor reached 9999 on 2025-02-21 and restarted at 1000 on 2025-02-22,
+    so each factor corresponds to a date every 9000 days.
+    */
+    pub fn vencimento_com_referencia(&self, reference: NaiveDate) -> Option<NaiveDate> {
+        let fator = i64::from(self.fator_vencimento());
+
+        if fator == 0 {
+            return None;
+        }
+
+        let reference_days = (reference - FATOR_BASE).num_days();
+        let ciclos = ((reference_days - fator) as f64 / FATOR_CICLO as f64)
+            .round()
+            .max(0.0);
+        let days = fator + ciclos as i64 * FATOR_CICLO;
+
+        FATOR_BASE.checked_add_days(Days::new(days as u64))
+    }
+
+    /// Due date, in the factor cycle closest to today.
+    pub fn vencimento(&self) -> Option<NaiveDate> {
+        self.vencimento_com_referencia(Local::now().date_naive())
+    }
+
+    /// Amount (valor), with 2 decimal places.
+    pub fn valor(&self) -> Decimal {
+        let cents: i128 = self.codigo_de_barras()[9..19].parse().unwrap_or_default();
+        Decimal::new(cents, 2)
+    }
+
+    /// Free field (campo livre), 25 digits defined by the bank.
+    pub fn campo_livre(&self) -> &str {
+        &self.codigo_de_barras()[19..]
+    }
+}
+
+impl FromStr for Boleto {
+    type Err = BoletoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Boleto::parse(s)
+    }
+}
+
+impl fmt::Display for Boleto {
+    /// Digitable line with the mask: "00190.50095 40144.816069 06809.350314 3 37370000000100".
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let l = self.linha_digitavel();
+        write!(
+            f,
+            "{}.{} {}.{} {}.{} {} {}",
+            &l[0..5],
+            &l[5..10],
+            &l[10..15],
+            &l[15..21],
+            &l[21..26],
+            &l[26..32],
+            &l[32..33],
+            &l[33..]
+        )
+    }
+}
+
+/**
+Collection slip (guia de arrecadação) of utilities and taxes, FEBRABAN layout.
+
+Parsed from the 48-digit digitable line or the 44-digit barcode starting with 8.
+
+The value identifier (3rd digit) selects the check digit:
+6 or 7 for mod-10, 8 or 9 for mod-11.
+With 6 or 8, the amount is the effective value in reais.
+
+Example:
+```
+    use claudiofsr_lib::{Decimal, GuiaArrecadacao};
+
+    let guia: GuiaArrecadacao = "836400000011 123400481008 123456789015 000000000018"
+        .parse()
+        .unwrap();
+
+    assert_eq!(guia.codigo_de_barras(), "83640000001123400481001234567890100000000001");
+    assert_eq!(guia.segmento(), 3);
+    assert_eq!(guia.valor(), Some(Decimal::new(11234, 2)));
+    assert_eq!(guia.empresa(), "0048");
+    assert_eq!(guia.to_string(), "83640000001-1 12340048100-8 12345678901-5 00000000001-8");
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GuiaArrecadacao([u8; BARCODE_LEN]);
+
+impl GuiaArrecadacao {
+    /// Parse the digitable line or the barcode, with or without the mask.
+    pub fn parse(text: &str) -> Result<Self, BoletoError> {
+        let digits = normalize(text)?;
+
+        match digits.len() {
+            48 => GuiaArrecadacao::from_linha_digitavel(&digits),
+            BARCODE_LEN => GuiaArrecadacao::from_codigo_de_barras(&digits),
+            len => Err(BoletoError::InvalidLength(len)),
+        }
+    }
+
+    /// Parse the 48-digit digitable line: 4 blocks of 11 digits and a check digit.
+    pub fn from_linha_digitavel(linha: &str) -> Result<Self, BoletoError> {
+        let linha = normalize(linha)?;
+
+        if linha.len() != 48 {
+            return Err(BoletoError::InvalidLength(linha.len()));
+        }
+
+        let check_digit = value_check_digit(&linha)?;
+
+        for field in 1..=4 {
+            let start = (field - 1) * 12;
+            check_field(field, &linha[start..start + 12], check_digit)?;
+        }
+
+        let barcode: String = (0..4)
+            .map(|block| &linha[block * 12..block * 12 + 11])
+            .collect();
+
+        GuiaArrecadacao::from_codigo_de_barras(&barcode)
+    }
+
+    /// Parse the 44-digit barcode, checking the general check digit.
+    pub fn from_codigo_de_barras(barcode: &str) -> Result<Self, BoletoError> {
+        let barcode = normalize(barcode)?;
+
+        if barcode.len() != BARCODE_LEN {
+            return Err(BoletoError::InvalidLength(barcode.len()));
+        }
+
+        match barcode.chars().next() {
+            Some('8') => (),
+            Some(ch) => return Err(BoletoError::InvalidProduct(ch)),
+            None => return Err(BoletoError::InvalidLength(0)),
+        }
+
+        let check_digit = value_check_digit(&barcode)?;
+        let expected = general_check_digit_at(&barcode, 3, check_digit);
+        let found = barcode.as_bytes()[3] - b'0';
+
+        if expected != found {
+            return Err(BoletoError::InvalidCheckDigit { expected, found });
+        }
+
+        let mut bytes = [0; BARCODE_LEN];
+        bytes.copy_from_slice(barcode.as_bytes());
+        Ok(GuiaArrecadacao(bytes))
+    }
+
+    /// The 44 digits of the barcode.
+    pub fn codigo_de_barras(&self) -> &str {
+        // Only ASCII digits are stored.
+        std::str::from_utf8(&self.0).unwrap_or_default()
+    }
+
+    /// The 48 digits of the digitable line, without the mask.
+    pub fn linha_digitavel(&self) -> String {
+        let barcode = self.codigo_de_barras();
+        let check_digit = value_check_digit(barcode).unwrap_or(CheckDigit::MOD10);
+
+        (0..4)
+            .map(|block| with_check_digit(&barcode[block * 11..(block + 1) * 11], check_digit))
+            .collect()
+    }
+
+    /// Segment: 1 prefeituras, 2 saneamento, 3 energia e gás, 4 telecomunicações,
+    /// 5 órgãos governamentais, 6 carnês, 7 multas de trânsito, 9 uso exclusivo do banco.
+    pub fn segmento(&self) -> u8 {
+        self.0[1] - b'0'
+    }
+
+    /// Value identifier: 6 or 8 for the effective value, 7 or 9 for a reference quantity.
+    pub fn identificador_valor(&self) -> u8 {
+        self.0[2] - b'0'
+    }
+
+    /// General check digit, the 4th digit of the barcode.
+    pub fn digito_verificador(&self) -> u8 {
+        self.0[3] - b'0'
+    }
+
+    /// Amount in reais, if the value identifier is 6 or 8.
+    pub fn valor(&self) -> Option<Decimal> {
+        match self.identificador_valor() {
+            6 | 8 => {
+                let cents: i128 = self.codigo_de_barras()[4..15].parse().ok()?;
+                Some(Decimal::new(cents, 2))
+            }
+            _ => None,
+        }
+    }
+
+    /// Company or agency (empresa/órgão) identification, 4 digits.
+    ///
+    /// In segment 6 (carnês), the first 8 digits of the CNPJ start here.
+    pub fn empresa(&self) -> &str {
+        &self.codigo_de_barras()[15..19]
+    }
+
+    /// Free field, from the 20th digit on.
+    pub fn campo_livre(&self) -> &str {
+        &self.codigo_de_barras()[19..]
+    }
+}
+
+impl FromStr for GuiaArrecadacao {
+    type Err = BoletoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        GuiaArrecadacao::parse(s)
+    }
+}
+
+impl fmt::Display for GuiaArrecadacao {
+    /// Digitable line with the mask: "83620000001-5 12340048100-3 ...".
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let linha = self.linha_digitavel();
+        let blocks: Vec<String> = (0..4)
+            .map(|block| {
+                let start = block * 12;
+                format!(
+                    "{}-{}",
+                    &linha[start..start + 11],
+                    &linha[start + 11..start + 12]
+                )
+            })
+            .collect();
+        write!(f, "{}", blocks.join(" "))
+    }
+}
+
+/// Bank boleto or collection slip, identified by the length and the first digit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CodigoBoleto {
+    Bancario(Boleto),
+    Arrecadacao(GuiaArrecadacao),
+}
+
+impl CodigoBoleto {
+    /**
+    Parse a digitable line (47 or 48 digits) or a barcode (44 digits).
+    ```
+        use claudiofsr_lib::CodigoBoleto;
+
+        let codigo = CodigoBoleto::parse("00193373700000001000500940144816060680935031").unwrap();
+        assert!(matches!(codigo, CodigoBoleto::Bancario(_)));
+
+        let codigo = CodigoBoleto::parse("83640000001123400481001234567890100000000001").unwrap();
+        assert!(matches!(codigo, CodigoBoleto::Arrecadacao(_)));
+    ```
+    */
+    pub fn parse(text: &str) -> Result<Self, BoletoError> {
+        let digits = normalize(text)?;
+
+        match (digits.len(), digits.starts_with('8')) {
+            (47, _) | (BARCODE_LEN, false) => Boleto::parse(&digits).map(CodigoBoleto::Bancario),
+            (48, _) | (BARCODE_LEN, true) => {
+                GuiaArrecadacao::parse(&digits).map(CodigoBoleto::Arrecadacao)
+            }
+            (len, _) => Err(BoletoError::InvalidLength(len)),
+        }
+    }
+
+    /// The 44 digits of the barcode.
+    pub fn codigo_de_barras(&self) -> &str {
+        match self {
+            CodigoBoleto::Bancario(boleto) => boleto.codigo_de_barras(),
+            CodigoBoleto::Arrecadacao(guia) => guia.codigo_de_barras(),
+        }
+    }
+
+    /// The digitable line, without the mask.
+    pub fn linha_digitavel(&self) -> String {
+        match self {
+            CodigoBoleto::Bancario(boleto) => boleto.linha_digitavel(),
+            CodigoBoleto::Arrecadacao(guia) => guia.linha_digitavel(),
+        }
+    }
+}
+
+impl FromStr for CodigoBoleto {
+    type Err = BoletoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        CodigoBoleto::parse(s)
+    }
+}
+
+impl fmt::Display for CodigoBoleto {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodigoBoleto::Bancario(boleto) => write!(f, "{boleto}"),
+            CodigoBoleto::Arrecadacao(guia) => write!(f, "{guia}"),
+        }
+    }
+}
+
+/**
+Due date factor (fator de vencimento) of a date, from 1000 to 9999.
+
+Returns `None` for dates before 2000-07-03 (factor 1000 of the first cycle).
+```
+    use claudiofsr_lib::fator_vencimento;
+    use chrono::NaiveDate;
+
+    let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+    assert_eq!(fator_vencimento(date(2007, 12, 31)), Some(3737));
+    assert_eq!(fator_vencimento(date(2025, 2, 21)), Some(9999));
+    assert_eq!(fator_vencimento(date(2025, 2, 22)), Some(1000));
+    assert_eq!(fator_vencimento(date(2000, 7, 2)), None);
+```
+*/
+pub fn fator_vencimento(date: NaiveDate) -> Option<u16> {
+    let days = (date - FATOR_BASE).num_days();
+
+    if days < 1000 {
+        return None;
+    }
+
+    u16::try_from((days - 1000) % FATOR_CICLO + 1000).ok()
+}
+
+/// Keep only the digits, removing spaces, dots and hyphens.
+fn normalize(text: &str) -> Result<String, BoletoError> {
+    let mut digits = String::with_capacity(48);
+
+    for ch in text.trim().chars() {
+        match ch {
+            ' ' | '.' | '-' => continue,
+            c if c.is_ascii_digit() => digits.push(c),
+            c => return Err(BoletoError::InvalidCharacter(c)),
+        }
+    }
+
+    Ok(digits)
+}
+
+/// Mod-10 or mod-11 of a collection slip, from the value identifier (3rd digit).
+fn value_check_digit(digits: &str) -> Result<CheckDigit, BoletoError> {
+    match digits.chars().nth(2) {
+        Some('6' | '7') => Ok(CheckDigit::MOD10),
+        Some('8' | '9') => Ok(CheckDigit::MOD11),
+        Some(ch) => Err(BoletoError::InvalidValueIdentifier(ch)),
+        None => Err(BoletoError::InvalidLength(digits.len())),
+    }
+}
+
+/// Field of the digitable line: digits followed by the check digit.
+fn check_field(field: usize, digits: &str, check_digit: CheckDigit) -> Result<(), BoletoError> {
+    let (base, dv) = digits.split_at(digits.len() - 1);
+    let expected = check_digit.compute(base).unwrap_or_default() as u8;
+    let found = dv.as_bytes()[0] - b'0';
+
+    match expected == found {
+        true => Ok(()),
+        false => Err(BoletoError::InvalidFieldCheckDigit {
+            field,
+            expected,
+            found,
+        }),
+    }
+}
+
+fn with_check_digit(digits: &str, check_digit: CheckDigit) -> String {
+    format!(
+        "{digits}{}",
+        check_digit.compute(digits).unwrap_or_default()
+    )
+}
+
+/// Check digit of the barcode of a bank boleto (5th digit).
+fn general_check_digit(barcode: &str, check_digit: CheckDigit) -> u8 {
+    general_check_digit_at(barcode, 4, check_digit)
+}
+
+/// Check digit of the barcode, computed without the digit at `index`.
+fn general_check_digit_at(barcode: &str, index: usize, check_digit: CheckDigit) -> u8 {
+    let base = [&barcode[..index], &barcode[index + 1..]].concat();
+    check_digit.compute(&base).unwrap_or_default() as u8
+}
+
+#[cfg(test)]
+mod boleto_tests {
+    use super::*;
+
+    // cargo test -- --show-output boleto_tests
+
+    const LINHA: &str = "00190500954014481606906809350314337370000000100";
+    const BARCODE: &str = "00193373700000001000500940144816060680935031";
+
+    #[test]
+    fn boleto_bancario() {
+        let from_linha = Boleto::parse(LINHA).unwrap();
+        let from_barcode = Boleto::parse(BARCODE).unwrap();
+
+        assert_eq!(from_linha, from_barcode);
+        assert_eq!(from_barcode.linha_digitavel(), LINHA);
+        assert_eq!(from_linha.moeda(), 9);
+        assert_eq!(from_linha.digito_verificador(), 3);
+
+        let boleto = Boleto::new(
+            "001",
+            NaiveDate::from_ymd_opt(2007, 12, 31),
+            Decimal::new(1, 0),
+            "0500940144816060680935031",
+        )
+        .unwrap();
+        assert_eq!(boleto, from_linha);
+
+        let boleto = Boleto::new("237", None, Decimal::ZERO, "0".repeat(25).as_str()).unwrap();
+        assert_eq!(boleto.vencimento(), None);
+        assert_eq!(
+            Boleto::new(
+                "237",
+                None,
+                Decimal::new(10_000_000_000, 2),
+                &"0".repeat(25)
+            ),
+            Err(BoletoError::InvalidAmount(Decimal::new(10_000_000_000, 2)))
+        );
+    }
+
+    #[test]
+    fn fator_vencimento_rollover() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let campo_livre = "1".repeat(25);
+
+        for vencimento in [date(2025, 2, 21), date(2025, 2, 22), date(2049, 10, 14)] {
+            let boleto =
+                Boleto::new("104", Some(vencimento), Decimal::new(5, 1), &campo_livre).unwrap();
+            let reference = vencimento - Days::new(30);
+            assert_eq!(
+                boleto.vencimento_com_referencia(reference),
+                Some(vencimento)
+            );
+        }
+
+        assert_eq!(fator_vencimento(date(2049, 10, 13)), Some(9999));
+        assert_eq!(fator_vencimento(date(2049, 10, 14)), Some(1000));
+    }
+
+    #[test]
+    fn guia_arrecadacao_mod11() {
+        let guia =
+            GuiaArrecadacao::parse("858800000016 123400481001 123456789010 000000000019").unwrap();
+
+        assert_eq!(guia.identificador_valor(), 8);
+        assert_eq!(guia.digito_verificador(), 8);
+        assert_eq!(guia.valor(), Some(Decimal::new(11234, 2)));
+        assert_eq!(guia.campo_livre(), "1001234567890100000000001");
+
+        let codigo: CodigoBoleto = guia.codigo_de_barras().parse().unwrap();
+        assert_eq!(codigo, CodigoBoleto::Arrecadacao(guia));
+        assert_eq!(
+            codigo.linha_digitavel(),
+            "858800000016123400481001123456789010000000000019"
+        );
+
+        assert_eq!(
+            GuiaArrecadacao::parse("858800000016 123400481002 123456789010 000000000019"),
+            Err(BoletoError::InvalidFieldCheckDigit {
+                field: 2,
+                expected: 1,
+                found: 2
+            })
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let mut linha = LINHA.to_string();
+        linha.replace_range(9..10, "4");
+        assert_eq!(
+            Boleto::parse(&linha),
+            Err(BoletoError::InvalidFieldCheckDigit {
+                field: 1,
+                expected: 5,
+                found: 4
+            })
+        );
+
+        let mut barcode = BARCODE.to_string();
+        barcode.replace_range(4..5, "7");
+        assert_eq!(
+            Boleto::parse(&barcode),
+            Err(BoletoError::InvalidCheckDigit {
+                expected: 3,
+                found: 7
+            })
+        );
+
+        assert_eq!(Boleto::parse("0019"), Err(BoletoError::InvalidLength(4)));
+        assert_eq!(
+            Boleto::parse("0019x"),
+            Err(BoletoError::InvalidCharacter('x'))
+        );
+        assert_eq!(
+            GuiaArrecadacao::parse(BARCODE),
+            Err(BoletoError::InvalidProduct('0'))
+        );
+        assert_eq!(
+            CodigoBoleto::parse("83520000001123400481001234567890100000000001"),
+            Err(BoletoError::InvalidValueIdentifier('5'))
+        );
+    }
+}
@@ -0,0 +1,479 @@
+use crate::{open_file, DateError, DateParser};
+use chrono::{Datelike, NaiveDate, Weekday};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    io::{self, BufRead, BufReader},
+    path::Path,
+};
+
+/// Fixed national holidays: (month, day, name).
+const FERIADOS_NACIONAIS: [(u32, u32, &str); 9] = [
+    (1, 1, "Confraternização Universal"),
+    (4, 21, "Tiradentes"),
+    (5, 1, "Dia do Trabalho"),
+    (9, 7, "Independência do Brasil"),
+    (10, 12, "Nossa Senhora Aparecida"),
+    (11, 2, "Finados"),
+    (11, 15, "Proclamação da República"),
+    (11, 20, "Dia Nacional de Zumbi e da Consciência Negra"),
+    (12, 25, "Natal"),
+];
+
+/// National holiday since 2024 (Lei nº 14.759/2023).
+const CONSCIENCIA_NEGRA_DESDE: i32 = 2024;
+
+/// Holidays relative to Easter Sunday: (days, name).
+const FERIADOS_MOVEIS: [(i64, &str); 4] = [
+    (-48, "Carnaval"),
+    (-47, "Carnaval"),
+    (-2, "Sexta-feira Santa"),
+    (60, "Corpus Christi"),
+];
+
+/// Errors returned when loading a holiday file.
+#[derive(Debug)]
+pub enum CalendarError {
+    /// Failure reading the holiday file.
+    Io(io::Error),
+    /// Line without a date and a name.
+    InvalidLine { line_number: usize, line: String },
+    /// Date that could not be parsed.
+    InvalidDate {
+        line_number: usize,
+        error: DateError,
+    },
+    /// Annual holiday "DD/MM" that does not exist.
+    InvalidAnnualDate { line_number: usize, text: String },
+}
+
+impl fmt::Display for CalendarError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CalendarError::Io(error) => write!(f, "io error: {error}"),
+            CalendarError::InvalidLine { line_number, line } => {
+                write!(
+                    f,
+                    "line {line_number}: expected a date and a name: '{line}'"
+                )
+            }
+            CalendarError::InvalidDate { line_number, error } => {
+                write!(f, "line {line_number}: {error}")
+            }
+            CalendarError::InvalidAnnualDate { line_number, text } => {
+                write!(f, "line {line_number}: invalid annual date DD/MM: '{text}'")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CalendarError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CalendarError::Io(error) => Some(error),
+            CalendarError::InvalidDate { error, .. } => Some(error),
+            CalendarError::InvalidLine { .. } | CalendarError::InvalidAnnualDate { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for CalendarError {
+    fn from(error: io::Error) -> Self {
+        CalendarError::Io(error)
+    }
+}
+
+/// How to move a due date that falls on a non-business day.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DueDateRule {
+    /// Move to the next business day.
+    #[default]
+    Postpone,
+    /// Move to the previous business day.
+    Anticipate,
+}
+
+/**
+Calendar of business days with Brazilian national holidays.
+
+Saturdays, Sundays, fixed national holidays, Carnaval (Monday and Tuesday),
+Sexta-feira Santa and Corpus Christi are not business days.
+
+State and municipal holidays can be added with [`BusinessCalendar::with_holiday`],
+[`BusinessCalendar::with_annual_holiday`] or loaded from a file.
+
+Example:
+```
+    use claudiofsr_lib::{BusinessCalendar, DueDateRule};
+    use chrono::NaiveDate;
+
+    let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+    let calendar = BusinessCalendar::new();
+
+    // Sexta-feira Santa
+    assert!(!calendar.is_business_day(date(2024, 3, 29)));
+    assert_eq!(calendar.holiday_name(date(2024, 3, 29)), Some("Sexta-feira Santa"));
+
+    // Thursday before Sexta-feira Santa + 1 business day = Monday
+    assert_eq!(calendar.add_business_days(date(2024, 3, 28), 1), Some(date(2024, 4, 1)));
+
+    // Due date on Saturday, 2024-09-07 (Independência)
+    let due_date = date(2024, 9, 7);
+    assert_eq!(calendar.adjust(due_date, DueDateRule::Postpone), Some(date(2024, 9, 9)));
+    assert_eq!(calendar.adjust(due_date, DueDateRule::Anticipate), Some(date(2024, 9, 6)));
+
+    let calendar = BusinessCalendar::new().with_annual_holiday(1, 25, "Aniversário de São Paulo");
+    assert!(calendar.is_holiday(date(2024, 1, 25)));
+```
+*/
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BusinessCalendar {
+    annual: BTreeMap<(u32, u32), String>,
+    dated: BTreeMap<NaiveDate, String>,
+}
+
+impl BusinessCalendar {
+    /// Calendar with national holidays only.
+    pub fn new() -> Self {
+        BusinessCalendar::default()
+    }
+
+    /// Add a holiday on a specific date.
+    pub fn with_holiday(mut self, date: NaiveDate, name: &str) -> Self {
+        self.dated.insert(date, name.to_string());
+        self
+    }
+
+    /// Add a holiday on the same month and day every year.
+    pub fn with_annual_holiday(mut self, month: u32, day: u32, name: &str) -> Self {
+        self.annual.insert((month, day), name.to_string());
+        self
+    }
+
+    /**
+    Load extra holidays from a file, one per line: the date and the name.
+
+    The date is "DD/MM" for annual holidays, or any format of [`DateParser`].
+
+    Blank lines and lines starting with `#` are ignored.
+    ```text
+        # São Paulo
+        25/01 Aniversário de São Paulo
+        09/07 Revolução Constitucionalista
+        2024-11-20 Consciência Negra
+    ```
+    */
+    pub fn load_holidays<P>(self, path: P) -> Result<Self, CalendarError>
+    where
+        P: AsRef<Path>,
+    {
+        let reader = BufReader::new(open_file(path)?);
+        let mut calendar = self;
+
+        for (index, line) in reader.lines().enumerate() {
+            calendar = calendar.parse_line(index + 1, &line?)?;
+        }
+
+        Ok(calendar)
+    }
+
+    /// Load extra holidays from text in the format of [`BusinessCalendar::load_holidays`].
+    pub fn parse_holidays(self, text: &str) -> Result<Self, CalendarError> {
+        let mut calendar = self;
+
+        for (index, line) in text.lines().enumerate() {
+            calendar = calendar.parse_line(index + 1, line)?;
+        }
+
+        Ok(calendar)
+    }
+
+    fn parse_line(self, line_number: usize, line: &str) -> Result<Self, CalendarError> {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            return Ok(self);
+        }
+
+        let invalid_line = || CalendarError::InvalidLine {
+            line_number,
+            line: line.to_string(),
+        };
+
+        let (date, name) = line
+            .split_once(char::is_whitespace)
+            .ok_or_else(invalid_line)?;
+        let name = name.trim();
+
+        if let Some((day, month)) = date
+            .split_once('/')
+            .filter(|(_, month)| !month.contains('/'))
+        {
+            // Leap day is valid as an annual holiday.
+            return match (day.parse(), month.parse()) {
+                (Ok(day), Ok(month)) if NaiveDate::from_ymd_opt(2024, month, day).is_some() => {
+                    Ok(self.with_annual_holiday(month, day, name))
+                }
+                _ => Err(CalendarError::InvalidAnnualDate {
+                    line_number,
+                    text: date.to_string(),
+                }),
+            };
+        }
+
+        match DateParser::new().parse(date) {
+            Ok(date) => Ok(self.with_holiday(date, name)),
+            Err(error) => Err(CalendarError::InvalidDate { line_number, error }),
+        }
+    }
+
+    /// Name of the holiday on the date, national or extra.
+    pub fn holiday_name(&self, date: NaiveDate) -> Option<&str> {
+        let (year, month, day) = (date.year(), date.month(), date.day());
+
+        let nacional = FERIADOS_NACIONAIS.iter().find(|&&(m, d, _)| {
+            (m, d) == (month, day) && ((m, d) != (11, 20) || year >= CONSCIENCIA_NEGRA_DESDE)
+        });
+
+        if let Some((_, _, name)) = nacional {
+            return Some(name);
+        }
+
+        if let Some(easter) = easter_sunday(year) {
+            let days = (date - easter).num_days();
+            let movel = FERIADOS_MOVEIS.iter().find(|(d, _)| *d == days);
+
+            if let Some((_, name)) = movel {
+                return Some(name);
+            }
+        }
+
+        self.dated
+            .get(&date)
+            .or_else(|| self.annual.get(&(month, day)))
+            .map(String::as_str)
+    }
+
+    /// National holiday (fixed or Easter-based) or an extra holiday added to this calendar.
+    pub fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.holiday_name(date).is_some()
+    }
+
+    /// Saturday or Sunday.
+    pub fn is_weekend(&self, date: NaiveDate) -> bool {
+        matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+    }
+
+    /// Neither a weekend nor a holiday.
+    pub fn is_business_day(&self, date: NaiveDate) -> bool {
+        !self.is_weekend(date) && !self.is_holiday(date)
+    }
+
+    /// First business day after the date.
+    pub fn next_business_day(&self, date: NaiveDate) -> Option<NaiveDate> {
+        let mut date = date.succ_opt()?;
+        while !self.is_business_day(date) {
+            date = date.succ_opt()?;
+        }
+        Some(date)
+    }
+
+    /// Last business day before the date.
+    pub fn previous_business_day(&self, date: NaiveDate) -> Option<NaiveDate> {
+        let mut date = date.pred_opt()?;
+        while !self.is_business_day(date) {
+            date = date.pred_opt()?;
+        }
+        Some(date)
+    }
+
+    /// Move the date by `days` business days, forward or backward.
+    ///
+    /// Zero days returns the date itself, even if it is not a business day.
+    pub fn add_business_days(&self, date: NaiveDate, days: i64) -> Option<NaiveDate> {
+        let mut date = date;
+        for _ in 0..days.unsigned_abs() {
+            date = match days > 0 {
+                true => self.next_business_day(date)?,
+                false => self.previous_business_day(date)?,
+            };
+        }
+        Some(date)
+    }
+
+    /// The date itself if it is a business day, otherwise moved by the rule.
+    pub fn adjust(&self, date: NaiveDate, rule: DueDateRule) -> Option<NaiveDate> {
+        match (self.is_business_day(date), rule) {
+            (true, _) => Some(date),
+            (false, DueDateRule::Postpone) => self.next_business_day(date),
+            (false, DueDateRule::Anticipate) => self.previous_business_day(date),
+        }
+    }
+
+    /// All holidays of the year, national and extra, sorted by date.
+    pub fn holidays(&self, year: i32) -> Vec<(NaiveDate, &str)> {
+        let Some(first_day) = NaiveDate::from_ymd_opt(year, 1, 1) else {
+            return Vec::new();
+        };
+
+        first_day
+            .iter_days()
+            .take_while(|date| date.year() == year)
+            .filter_map(|date| self.holiday_name(date).map(|name| (date, name)))
+            .collect()
+    }
+}
+
+/**
+Easter Sunday of the Gregorian calendar (anonymous Gregorian algorithm).
+
+Example:
+```
+    use claudiofsr_lib::easter_sunday;
+    use chrono::NaiveDate;
+
+    assert_eq!(easter_sunday(2024), NaiveDate::from_ymd_opt(2024, 3, 31));
+    assert_eq!(easter_sunday(2025), NaiveDate::from_ymd_opt(2025, 4, 20));
+```
+*/
+pub fn easter_sunday(year: i32) -> Option<NaiveDate> {
+    let a = year.rem_euclid(19);
+    let b = year.div_euclid(100);
+    let c = year.rem_euclid(100);
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32)
+}
+
+#[cfg(test)]
+mod calendar_tests {
+    use super::*;
+
+    // cargo test -- --show-output calendar_tests
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn national_holidays() {
+        let calendar = BusinessCalendar::new();
+
+        assert_eq!(easter_sunday(2000), Some(date(2000, 4, 23)));
+        assert_eq!(easter_sunday(2019), Some(date(2019, 4, 21)));
+
+        let holidays: Vec<NaiveDate> = calendar.holidays(2025).iter().map(|(d, _)| *d).collect();
+        assert_eq!(
+            holidays,
+            [
+                date(2025, 1, 1),
+                date(2025, 3, 3),
+                date(2025, 3, 4),
+                date(2025, 4, 18),
+                date(2025, 4, 21),
+                date(2025, 5, 1),
+                date(2025, 6, 19),
+                date(2025, 9, 7),
+                date(2025, 10, 12),
+                date(2025, 11, 2),
+                date(2025, 11, 15),
+                date(2025, 11, 20),
+                date(2025, 12, 25),
+            ]
+        );
+
+        assert!(calendar.is_business_day(date(2023, 11, 20)));
+        assert!(!calendar.is_business_day(date(2024, 11, 20)));
+    }
+
+    #[test]
+    fn business_days() {
+        let calendar = BusinessCalendar::new();
+
+        // Friday
+        assert_eq!(
+            calendar.next_business_day(date(2024, 12, 20)),
+            Some(date(2024, 12, 23))
+        );
+        // Christmas on Wednesday
+        assert_eq!(
+            calendar.add_business_days(date(2024, 12, 24), 1),
+            Some(date(2024, 12, 26))
+        );
+        assert_eq!(
+            calendar.add_business_days(date(2024, 12, 26), -1),
+            Some(date(2024, 12, 24))
+        );
+        assert_eq!(
+            calendar.add_business_days(date(2024, 12, 28), 0),
+            Some(date(2024, 12, 28))
+        );
+        // Carnaval
+        assert_eq!(
+            calendar.add_business_days(date(2024, 2, 9), 1),
+            Some(date(2024, 2, 14))
+        );
+        assert_eq!(
+            calendar.previous_business_day(date(2024, 2, 14)),
+            Some(date(2024, 2, 9))
+        );
+        assert_eq!(calendar.previous_business_day(NaiveDate::MIN), None);
+    }
+
+    #[test]
+    fn extra_holidays() -> Result<(), CalendarError> {
+        let text = "
+            # São Paulo
+            25/01 Aniversário de São Paulo
+            09/07 Revolução Constitucionalista
+            2024-07-08 Ponto facultativo
+        ";
+
+        let calendar = BusinessCalendar::new().parse_holidays(text)?;
+
+        assert_eq!(
+            calendar.holiday_name(date(2030, 1, 25)),
+            Some("Aniversário de São Paulo")
+        );
+        // Monday and Tuesday
+        assert_eq!(
+            calendar.adjust(date(2024, 7, 8), DueDateRule::Postpone),
+            Some(date(2024, 7, 10))
+        );
+        assert!(calendar.is_business_day(date(2025, 7, 8)));
+
+        let error = BusinessCalendar::new()
+            .parse_holidays("\n31/02 Nada")
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2: invalid annual date DD/MM: '31/02'"
+        );
+
+        let error = BusinessCalendar::new().parse_holidays("25/01").unwrap_err();
+        assert!(matches!(
+            error,
+            CalendarError::InvalidLine { line_number: 1, .. }
+        ));
+
+        let error = BusinessCalendar::new()
+            .parse_holidays("2023-02-29 X")
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            CalendarError::InvalidDate { line_number: 1, .. }
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,556 @@
+use crate::{cnpj::CNPJ_CHECK_DIGIT, get_naive_date, Cnpj, Cpf, Uf};
+use chrono::{Datelike, NaiveDate};
+use std::{fmt, str::FromStr};
+
+/// Number of characters in an access key.
+const CHAVE_LEN: usize = 44;
+
+/// Errors returned when parsing or generating an access key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChaveError {
+    /// The number of characters, after removing whitespace, is not 44.
+    InvalidLength(usize),
+    /// A character other than a digit (or a letter in the CNPJ field) was found.
+    InvalidCharacter(char),
+    /// The first two digits are not an IBGE state code.
+    InvalidUf(u8),
+    /// The AAMM field is not a valid year and month.
+    InvalidAnoMes(String),
+    /// The emitter field is neither a valid CNPJ nor a valid CPF.
+    InvalidEmitente(String),
+    /// The document model is not supported.
+    InvalidModelo(u8),
+    /// A component is outside the range of its field, like nNF or tpEmis zero.
+    FieldOutOfRange { field: &'static str, value: u64 },
+    /// The check digit does not match the calculated one.
+    InvalidCheckDigit { expected: u8, found: u8 },
+}
+
+impl fmt::Display for ChaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChaveError::InvalidLength(len) => {
+                write!(
+                    f,
+                    "access key must have {CHAVE_LEN} characters, found {len}"
+                )
+            }
+            ChaveError::InvalidCharacter(ch) => {
+                write!(f, "invalid character in access key: {ch:?}")
+            }
+            ChaveError::InvalidUf(code) => write!(f, "invalid UF code in access key: {code}"),
+            ChaveError::InvalidAnoMes(aamm) => {
+                write!(f, "invalid year/month (AAMM) in access key: {aamm}")
+            }
+            ChaveError::InvalidEmitente(doc) => {
+                write!(f, "invalid emitter CNPJ/CPF in access key: {doc}")
+            }
+            ChaveError::InvalidModelo(modelo) => {
+                write!(f, "invalid document model in access key: {modelo}")
+            }
+            ChaveError::FieldOutOfRange { field, value } => {
+                write!(f, "access key field {field} out of range: {value}")
+            }
+            ChaveError::InvalidCheckDigit { expected, found } => {
+                write!(
+                    f,
+                    "invalid access key check digit: expected {expected}, found {found}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ChaveError {}
+
+/// Fiscal document model (campo `mod`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Modelo {
+    /// Nota Fiscal Eletrônica (NF-e)
+    NFe = 55,
+    /// Conhecimento de Transporte Eletrônico (CT-e)
+    CTe = 57,
+    /// Manifesto Eletrônico de Documentos Fiscais (MDF-e)
+    MDFe = 58,
+    /// Nota Fiscal de Consumidor Eletrônica (NFC-e)
+    NFCe = 65,
+    /// Conhecimento de Transporte Eletrônico para Outros Serviços (CT-e OS)
+    CTeOS = 67,
+}
+
+impl Modelo {
+    /// The two-digit code of the model.
+    pub fn code(self) -> u8 {
+        self as u8
+    }
+}
+
+impl TryFrom<u8> for Modelo {
+    type Error = ChaveError;
+
+    fn try_from(code: u8) -> Result<Self, Self::Error> {
+        match code {
+            55 => Ok(Modelo::NFe),
+            57 => Ok(Modelo::CTe),
+            58 => Ok(Modelo::MDFe),
+            65 => Ok(Modelo::NFCe),
+            67 => Ok(Modelo::CTeOS),
+            _ => Err(ChaveError::InvalidModelo(code)),
+        }
+    }
+}
+
+/// The emitter of the document: a company (CNPJ) or a person (CPF).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Emitente {
+    Cnpj(Cnpj),
+    Cpf(Cpf),
+}
+
+impl Emitente {
+    /// The 14 characters of the emitter field: CPF is left padded with zeros.
+    fn to_field(self) -> String {
+        match self {
+            Emitente::Cnpj(cnpj) => cnpj.as_str().to_string(),
+            Emitente::Cpf(cpf) => format!("000{}", cpf.as_str()),
+        }
+    }
+}
+
+impl fmt::Display for Emitente {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Emitente::Cnpj(cnpj) => write!(f, "{cnpj}"),
+            Emitente::Cpf(cpf) => write!(f, "{cpf}"),
+        }
+    }
+}
+
+/// Components of an access key, without the check digit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComponentesChave {
+    /// IBGE code of the state (cUF).
+    pub codigo_uf: u8,
+    /// Year and month of emission (AAMM), as the first day of the month.
+    pub ano_mes: NaiveDate,
+    /// CNPJ or CPF of the emitter.
+    pub emitente: Emitente,
+    /// Document model (mod).
+    pub modelo: Modelo,
+    /// Series (serie): 0 to 999.
+    pub serie: u16,
+    /// Document number (nNF): 1 to 999.999.999.
+    pub numero: u32,
+    /// Emission type (tpEmis): 1 to 9.
+    pub tipo_emissao: u8,
+    /// Numeric code (cNF): up to 8 digits.
+    pub codigo_numerico: u32,
+}
+
+/**
+Access key (chave de acesso) of NF-e, NFC-e, CT-e and MDF-e with 44 characters.
+
+Layout: cUF (2) + AAMM (4) + CNPJ/CPF (14) + mod (2) + serie (3) + nNF (9)
+\+ tpEmis (1) + cNF (8) + cDV (1).
+
+The check digit is a mod-11 with weights 2 to 9 from right to left.
+Letters of the alphanumeric CNPJ are valued as their ASCII code minus 48.
+
+Example:
+```
+    use claudiofsr_lib::{ChaveDeAcesso, Modelo, Uf};
+    use chrono::NaiveDate;
+
+    let chave: ChaveDeAcesso = "3524 0111 2223 3300 0181 5500 1000 0001 2311 2345 6780"
+        .parse()
+        .unwrap();
+
+    assert_eq!(chave.codigo_uf(), 35);
+    assert_eq!(chave.uf(), Uf::SP);
+    assert_eq!(chave.ano_mes(), NaiveDate::from_ymd_opt(2024, 1, 1).unwrap());
+    assert_eq!(chave.emitente().to_string(), "11.222.333/0001-81");
+    assert_eq!(chave.modelo(), Modelo::NFe);
+    assert_eq!(chave.serie(), 1);
+    assert_eq!(chave.numero(), 123);
+    assert_eq!(chave.tipo_emissao(), 1);
+    assert_eq!(chave.codigo_numerico(), 12345678);
+    assert_eq!(chave.digito_verificador(), 0);
+
+    // Generate the key from its components
+    let novo = ChaveDeAcesso::gerar(&chave.componentes()).unwrap();
+    assert_eq!(novo, chave);
+```
+
+<https://www.nfe.fazenda.gov.br/portal/exibirArquivo.aspx?conteudo=URCYvjVMVBM=>
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ChaveDeAcesso([u8; CHAVE_LEN]);
+
+impl ChaveDeAcesso {
+    /// Parse an access key, ignoring whitespace.
+    ///
+    /// If the emitter field is a valid CNPJ and also a CPF padded with zeros,
+    /// the CNPJ takes precedence.
+    pub fn parse(chave: &str) -> Result<Self, ChaveError> {
+        let chars: Vec<u8> = chave
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| match c {
+                c if c.is_ascii_alphanumeric() => Ok(c.to_ascii_uppercase() as u8),
+                c => Err(ChaveError::InvalidCharacter(c)),
+            })
+            .collect::<Result<_, _>>()?;
+
+        if chars.len() != CHAVE_LEN {
+            return Err(ChaveError::InvalidLength(chars.len()));
+        }
+
+        // Letters are only allowed in the CNPJ field.
+        let (before, after) = (&chars[..6], &chars[20..]);
+        if let Some(&ch) = before.iter().chain(after).find(|b| !b.is_ascii_digit()) {
+            return Err(ChaveError::InvalidCharacter(ch as char));
+        }
+
+        let mut bytes = [0; CHAVE_LEN];
+        bytes.copy_from_slice(&chars);
+        let chave = ChaveDeAcesso(bytes);
+
+        let codigo_uf = chave.number(0..2) as u8;
+        if Uf::from_codigo(codigo_uf).is_none() {
+            return Err(ChaveError::InvalidUf(codigo_uf));
+        }
+
+        ano_mes(&chave.as_str()[2..6])?;
+        emitente(&chave.as_str()[6..20])?;
+        Modelo::try_from(chave.number(20..22) as u8)?;
+
+        for (field, range) in [("nNF", 25..34), ("tpEmis", 34..35)] {
+            let value = u64::from(chave.number(range));
+            if value == 0 {
+                return Err(ChaveError::FieldOutOfRange { field, value });
+            }
+        }
+
+        let expected = check_digit(&bytes[..43]);
+        let found = bytes[43] - b'0';
+        if expected != found {
+            return Err(ChaveError::InvalidCheckDigit { expected, found });
+        }
+
+        Ok(chave)
+    }
+
+    /// Build an access key from its components, computing the check digit.
+    pub fn gerar(componentes: &ComponentesChave) -> Result<Self, ChaveError> {
+        let ComponentesChave {
+            codigo_uf,
+            ano_mes,
+            emitente,
+            modelo,
+            serie,
+            numero,
+            tipo_emissao,
+            codigo_numerico,
+        } = *componentes;
+
+        if Uf::from_codigo(codigo_uf).is_none() {
+            return Err(ChaveError::InvalidUf(codigo_uf));
+        }
+
+        let year = ano_mes.year();
+        if !(2000..=2099).contains(&year) {
+            let value = u64::try_from(year).unwrap_or_default();
+            return Err(ChaveError::FieldOutOfRange {
+                field: "AAMM",
+                value,
+            });
+        }
+
+        for (field, value, range) in [
+            ("serie", u64::from(serie), 0..=999),
+            ("nNF", u64::from(numero), 1..=999_999_999),
+            ("tpEmis", u64::from(tipo_emissao), 1..=9),
+            ("cNF", u64::from(codigo_numerico), 0..=99_999_999),
+        ] {
+            if !range.contains(&value) {
+                return Err(ChaveError::FieldOutOfRange { field, value });
+            }
+        }
+
+        let base = format!(
+            "{codigo_uf:02}{:02}{:02}{}{:02}{serie:03}{numero:09}{tipo_emissao}{codigo_numerico:08}",
+            year % 100,
+            ano_mes.month(),
+            emitente.to_field(),
+            modelo.code(),
+        );
+
+        let mut bytes = [0; CHAVE_LEN];
+        bytes[..43].copy_from_slice(base.as_bytes());
+        bytes[43] = check_digit(&bytes[..43]) + b'0';
+
+        Ok(ChaveDeAcesso(bytes))
+    }
+
+    /// The 44 characters of the key.
+    pub fn as_str(&self) -> &str {
+        // Only ASCII alphanumeric bytes are stored.
+        std::str::from_utf8(&self.0).unwrap_or_default()
+    }
+
+    /// IBGE code of the state (cUF).
+    pub fn codigo_uf(&self) -> u8 {
+        self.number(0..2) as u8
+    }
+
+    /// State of the emitter.
+    pub fn uf(&self) -> Uf {
+        Uf::from_codigo(self.codigo_uf()).expect("cUF validated on parse")
+    }
+
+    /// Year and month of emission (AAMM), as the first day of the month.
+    pub fn ano_mes(&self) -> NaiveDate {
+        ano_mes(&self.as_str()[2..6]).expect("AAMM validated on parse")
+    }
+
+    /// CNPJ or CPF of the emitter.
+    pub fn emitente(&self) -> Emitente {
+        emitente(&self.as_str()[6..20]).expect("CNPJ/CPF validated on parse")
+    }
+
+    /// Document model (mod).
+    pub fn modelo(&self) -> Modelo {
+        Modelo::try_from(self.number(20..22) as u8).expect("model validated on parse")
+    }
+
+    /// Series (serie).
+    pub fn serie(&self) -> u16 {
+        self.number(22..25) as u16
+    }
+
+    /// Document number (nNF).
+    pub fn numero(&self) -> u32 {
+        self.number(25..34)
+    }
+
+    /// Emission type (tpEmis): 1 normal, 2 to 9 contingency modes.
+    pub fn tipo_emissao(&self) -> u8 {
+        self.number(34..35) as u8
+    }
+
+    /// Numeric code (cNF).
+    pub fn codigo_numerico(&self) -> u32 {
+        self.number(35..43)
+    }
+
+    /// Check digit (cDV).
+    pub fn digito_verificador(&self) -> u8 {
+        self.0[43] - b'0'
+    }
+
+    /// All components of the key, except the check digit.
+    pub fn componentes(&self) -> ComponentesChave {
+        ComponentesChave {
+            codigo_uf: self.codigo_uf(),
+            ano_mes: self.ano_mes(),
+            emitente: self.emitente(),
+            modelo: self.modelo(),
+            serie: self.serie(),
+            numero: self.numero(),
+            tipo_emissao: self.tipo_emissao(),
+            codigo_numerico: self.codigo_numerico(),
+        }
+    }
+
+    /// Numeric value of a range of digits.
+    fn number(&self, range: std::ops::Range<usize>) -> u32 {
+        self.0[range]
+            .iter()
+            .fold(0, |acc, &b| acc * 10 + u32::from(b - b'0'))
+    }
+}
+
+impl FromStr for ChaveDeAcesso {
+    type Err = ChaveError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ChaveDeAcesso::parse(s)
+    }
+}
+
+impl fmt::Display for ChaveDeAcesso {
+    /// Format in groups of 4 characters, as printed in the DANFE.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let groups: Vec<&str> = self
+            .0
+            .chunks(4)
+            .map(|chunk| std::str::from_utf8(chunk).unwrap_or_default())
+            .collect();
+        write!(f, "{}", groups.join(" "))
+    }
+}
+
+/// Parse AAMM into the first day of the month.
+fn ano_mes(aamm: &str) -> Result<NaiveDate, ChaveError> {
+    let month: u32 = aamm[2..].parse().unwrap_or_default();
+    if !(1..=12).contains(&month) {
+        return Err(ChaveError::InvalidAnoMes(aamm.to_string()));
+    }
+
+    // DDMMYYYY
+    let ddmmyyyy = format!("01{}20{}", &aamm[2..], &aamm[..2]);
+    get_naive_date(ddmmyyyy).ok_or_else(|| ChaveError::InvalidAnoMes(aamm.to_string()))
+}
+
+/// Parse the 14 characters of the emitter field.
+fn emitente(field: &str) -> Result<Emitente, ChaveError> {
+    if let Ok(cnpj) = Cnpj::parse(field) {
+        return Ok(Emitente::Cnpj(cnpj));
+    }
+
+    match field.strip_prefix("000").map(Cpf::parse) {
+        Some(Ok(cpf)) => Ok(Emitente::Cpf(cpf)),
+        _ => Err(ChaveError::InvalidEmitente(field.to_string())),
+    }
+}
+
+/// Mod-11 with weights 2 to 9 from right to left.
+///
+/// Letters of the alphanumeric CNPJ are valued as ASCII code minus 48.
+fn check_digit(base: &[u8]) -> u8 {
+    CNPJ_CHECK_DIGIT
+        .compute(&String::from_utf8_lossy(base))
+        .expect("validated to 0-9 and A-Z") as u8
+}
+
+#[cfg(test)]
+mod chave_tests {
+    use super::*;
+
+    // cargo test -- --show-output chave_tests
+
+    #[test]
+    fn parse_chave_com_cnpj_alfanumerico() {
+        let chave = ChaveDeAcesso::parse("35260312ABC34501DE35650010000045671876543213").unwrap();
+
+        assert_eq!(
+            chave.ano_mes(),
+            NaiveDate::from_ymd_opt(2026, 3, 1).unwrap()
+        );
+        assert_eq!(chave.emitente().to_string(), "12.ABC.345/01DE-35");
+        assert_eq!(chave.modelo(), Modelo::NFCe);
+        assert_eq!(chave.numero(), 4567);
+        assert_eq!(chave.digito_verificador(), 3);
+        assert_eq!(
+            chave.to_string(),
+            "3526 0312 ABC3 4501 DE35 6500 1000 0045 6718 7654 3213"
+        );
+    }
+
+    #[test]
+    fn parse_chave_com_cpf() {
+        let chave = ChaveDeAcesso::parse("35240100052998224725550010000000011000000010").unwrap();
+        let cpf = Cpf::parse("529.982.247-25").unwrap();
+        assert_eq!(chave.emitente(), Emitente::Cpf(cpf));
+    }
+
+    #[test]
+    fn invalid_chave() {
+        let valid = "35240111222333000181550010000001231123456780";
+
+        assert_eq!(
+            ChaveDeAcesso::parse(&valid[..43]),
+            Err(ChaveError::InvalidLength(43))
+        );
+        assert_eq!(
+            ChaveDeAcesso::parse("99240111222333000181550010000001231123456780"),
+            Err(ChaveError::InvalidUf(99))
+        );
+        assert_eq!(
+            ChaveDeAcesso::parse(&format!("{}1", &valid[..43])),
+            Err(ChaveError::InvalidCheckDigit {
+                expected: 0,
+                found: 1
+            })
+        );
+        assert_eq!(
+            ChaveDeAcesso::parse("35241311222333000181550010000001231123456780"),
+            Err(ChaveError::InvalidAnoMes("2413".to_string()))
+        );
+        assert_eq!(
+            ChaveDeAcesso::parse("35240111222333000182550010000001231123456780"),
+            Err(ChaveError::InvalidEmitente("11222333000182".to_string()))
+        );
+        assert_eq!(
+            ChaveDeAcesso::parse("35240111222333000181990010000001231123456780"),
+            Err(ChaveError::InvalidModelo(99))
+        );
+
+        for (field, index) in [("nNF", 25..34), ("tpEmis", 34..35)] {
+            let mut base = valid.as_bytes()[..43].to_vec();
+            base[index].fill(b'0');
+            base.push(check_digit(&base) + b'0');
+            assert_eq!(
+                ChaveDeAcesso::parse(std::str::from_utf8(&base).unwrap()),
+                Err(ChaveError::FieldOutOfRange { field, value: 0 })
+            );
+        }
+    }
+
+    #[test]
+    fn gerar_chave() {
+        let componentes = ComponentesChave {
+            codigo_uf: 35,
+            ano_mes: NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+            emitente: Emitente::Cnpj(Cnpj::parse("12.ABC.345/01DE-35").unwrap()),
+            modelo: Modelo::NFCe,
+            serie: 1,
+            numero: 4567,
+            tipo_emissao: 1,
+            codigo_numerico: 87654321,
+        };
+
+        let chave = ChaveDeAcesso::gerar(&componentes).unwrap();
+        assert_eq!(
+            chave.as_str(),
+            "35260312ABC34501DE35650010000045671876543213"
+        );
+        assert_eq!(chave.componentes(), componentes);
+
+        let invalid = ComponentesChave {
+            serie: 1000,
+            ..componentes
+        };
+        assert_eq!(
+            ChaveDeAcesso::gerar(&invalid),
+            Err(ChaveError::FieldOutOfRange {
+                field: "serie",
+                value: 1000
+            })
+        );
+
+        let invalid = ComponentesChave {
+            numero: 0,
+            ..componentes
+        };
+        assert_eq!(
+            ChaveDeAcesso::gerar(&invalid),
+            Err(ChaveError::FieldOutOfRange {
+                field: "nNF",
+                value: 0
+            })
+        );
+
+        let invalid = ComponentesChave {
+            tipo_emissao: 0,
+            ..componentes
+        };
+        assert_eq!(
+            ChaveDeAcesso::gerar(&invalid),
+            Err(ChaveError::FieldOutOfRange {
+                field: "tpEmis",
+                value: 0
+            })
+        );
+    }
+}
@@ -0,0 +1,339 @@
+use std::fmt;
+
+/// Errors returned when the text has no valid value to compute the check digit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CheckDigitError {
+    /// No characters before the check digits.
+    Empty,
+    /// Character without a value in the [`CharValues`] mapping.
+    InvalidCharacter(char),
+}
+
+impl fmt::Display for CheckDigitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckDigitError::Empty => write!(f, "no characters to compute the check digit"),
+            CheckDigitError::InvalidCharacter(ch) => {
+                write!(f, "invalid character for check digit: {ch:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CheckDigitError {}
+
+/// End of the text where the first weight is applied.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    #[default]
+    RightToLeft,
+    LeftToRight,
+}
+
+/// Value of each character in the weighted sum.
+#[derive(Debug, Clone, Copy)]
+pub enum CharValues {
+    /// Only `0-9`.
+    Digits,
+    /// `0-9` and `A-Z`, valued as the ASCII code minus 48 (alphanumeric CNPJ).
+    AsciiMinus48,
+    /// Value given by the function, `None` for an invalid character.
+    Custom(fn(char) -> Option<u32>),
+}
+
+impl CharValues {
+    pub fn value(&self, ch: char) -> Option<u32> {
+        match self {
+            CharValues::Digits => ch.to_digit(10),
+            CharValues::AsciiMinus48 => match ch {
+                '0'..='9' | 'A'..='Z' => Some(ch as u32 - 48),
+                _ => None,
+            },
+            CharValues::Custom(value) => value(ch),
+        }
+    }
+}
+
+/// Conversion of the remainder of the weighted sum into the check digit.
+#[derive(Debug, Clone, Copy)]
+pub enum DigitRule {
+    /// `modulus - remainder`, or `overflow` if the result is 10 or more.
+    Complement {
+        overflow: u32,
+    },
+    /// The remainder itself, or `overflow` if it is 10 or more.
+    Remainder {
+        overflow: u32,
+    },
+    Custom(fn(u32) -> u32),
+}
+
+impl DigitRule {
+    pub fn digit(&self, remainder: u32, modulus: u32) -> u32 {
+        match *self {
+            DigitRule::Complement { overflow } => match modulus - remainder {
+                digit @ 0..=9 => digit,
+                _ => overflow,
+            },
+            DigitRule::Remainder { overflow } => match remainder {
+                digit @ 0..=9 => digit,
+                _ => overflow,
+            },
+            DigitRule::Custom(rule) => rule(remainder),
+        }
+    }
+}
+
+/**
+Weighted-sum check digit, like the mod-11 of CPF, CNPJ and NF-e keys
+or the mod-10 of boletos.
+
+The weights are cycled from the end given by the [`Direction`].
+
+Example:
+```
+    use claudiofsr_lib::{CheckDigit, DigitRule, Direction};
+
+    // CPF: mod-11, weights 2 to 11 from right to left, two digits.
+    const CPF: CheckDigit = CheckDigit::mod11(&[2, 3, 4, 5, 6, 7, 8, 9, 10, 11]);
+    assert_eq!(CPF.compute_n("111444777", 2), Ok(vec![3, 5]));
+    assert!(CPF.verify_n("11144477735", 2));
+
+    // PIS/NIT: weights 3, 2, 9, ..., 2 from left to right, 10 and 11 become 0.
+    const PIS: CheckDigit = CheckDigit::mod11(&[3, 2, 9, 8, 7, 6, 5, 4, 3, 2])
+        .with_direction(Direction::LeftToRight);
+    assert!(PIS.verify("12056412545"));
+
+    // Boleto: mod-10 of each field, weights 2 and 1, digits of the products summed.
+    assert_eq!(CheckDigit::MOD10.compute("001905009"), Ok(5));
+
+    // Boleto: general check digit, remainders 0 and 1 give 1.
+    const BOLETO: CheckDigit = CheckDigit::MOD11.with_rule(DigitRule::Complement { overflow: 1 });
+    assert_eq!(BOLETO.compute("0019373700000001000500940144816060680935031"), Ok(3));
+```
+*/
+#[derive(Debug, Clone, Copy)]
+pub struct CheckDigit {
+    modulus: u32,
+    weights: &'static [u32],
+    direction: Direction,
+    sum_product_digits: bool,
+    values: CharValues,
+    rule: DigitRule,
+}
+
+impl CheckDigit {
+    /// Mod-11 with weights 2 to 9 from right to left (NF-e key, boleto, CNPJ).
+    pub const MOD11: CheckDigit = CheckDigit::mod11(&[2, 3, 4, 5, 6, 7, 8, 9]);
+
+    /// Mod-10 with weights 2 and 1 from right to left, summing the digits of
+    /// each product (Luhn, boleto fields).
+    pub const MOD10: CheckDigit = CheckDigit::new(10, &[2, 1]).with_sum_product_digits(true);
+
+    /// Right to left, digits only, rule `modulus - remainder` with overflow 0.
+    ///
+    /// Panics if the modulus is zero.
+    pub const fn new(modulus: u32, weights: &'static [u32]) -> Self {
+        assert!(modulus > 0, "check digit modulus must not be zero");
+        CheckDigit {
+            modulus,
+            weights,
+            direction: Direction::RightToLeft,
+            sum_product_digits: false,
+            values: CharValues::Digits,
+            rule: DigitRule::Complement { overflow: 0 },
+        }
+    }
+
+    /// Mod-11: remainders 0 and 1 give 0, otherwise 11 - remainder.
+    pub const fn mod11(weights: &'static [u32]) -> Self {
+        CheckDigit::new(11, weights)
+    }
+
+    /// End of the text where the first weight is applied.
+    pub const fn with_direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Add the digits of each product instead of the product (14 -> 1 + 4).
+    pub const fn with_sum_product_digits(mut self, sum_product_digits: bool) -> Self {
+        self.sum_product_digits = sum_product_digits;
+        self
+    }
+
+    /// Value of each character, [`CharValues::Digits`] by default.
+    pub const fn with_values(mut self, values: CharValues) -> Self {
+        self.values = values;
+        self
+    }
+
+    /// Conversion of the remainder into the digit, `Complement { overflow: 0 }` by default.
+    pub const fn with_rule(mut self, rule: DigitRule) -> Self {
+        self.rule = rule;
+        self
+    }
+
+    /// Divisor of the weighted sum.
+    pub const fn modulus(&self) -> u32 {
+        self.modulus
+    }
+
+    /// Values of the characters of the text.
+    pub fn values(&self, text: &str) -> Result<Vec<u32>, CheckDigitError> {
+        text.chars()
+            .map(|ch| {
+                self.values
+                    .value(ch)
+                    .ok_or(CheckDigitError::InvalidCharacter(ch))
+            })
+            .collect()
+    }
+
+    /// Check digit of the values.
+    pub fn compute_values(&self, values: &[u32]) -> u32 {
+        let weights = self.weights.iter().cycle();
+
+        let weighted: Box<dyn Iterator<Item = (&u32, &u32)>> = match self.direction {
+            Direction::RightToLeft => Box::new(values.iter().rev().zip(weights)),
+            Direction::LeftToRight => Box::new(values.iter().zip(weights)),
+        };
+
+        let sum: u64 = weighted
+            .map(|(&value, &weight)| {
+                let product = u64::from(value) * u64::from(weight);
+                match self.sum_product_digits {
+                    true => sum_digits(product),
+                    false => product,
+                }
+            })
+            .sum();
+
+        let remainder = (sum % u64::from(self.modulus)) as u32;
+        self.rule.digit(remainder, self.modulus)
+    }
+
+    /// Check digit of the text.
+    pub fn compute(&self, base: &str) -> Result<u32, CheckDigitError> {
+        let values = self.values(base)?;
+
+        if values.is_empty() {
+            return Err(CheckDigitError::Empty);
+        }
+
+        Ok(self.compute_values(&values))
+    }
+
+    /// `count` check digits, each one computed with the previous ones appended.
+    pub fn compute_n(&self, base: &str, count: usize) -> Result<Vec<u32>, CheckDigitError> {
+        let mut values = self.values(base)?;
+
+        if values.is_empty() {
+            return Err(CheckDigitError::Empty);
+        }
+
+        let mut digits = Vec::with_capacity(count);
+        for _ in 0..count {
+            let digit = self.compute_values(&values);
+            values.push(digit);
+            digits.push(digit);
+        }
+
+        Ok(digits)
+    }
+
+    /// The text followed by `count` check digits.
+    pub fn append(&self, base: &str, count: usize) -> Result<String, CheckDigitError> {
+        let digits = self.compute_n(base, count)?;
+        let digits: String = digits.iter().map(|digit| digit.to_string()).collect();
+        Ok(format!("{base}{digits}"))
+    }
+
+    /// Returns true if the last character is the check digit of the others.
+    pub fn verify(&self, text: &str) -> bool {
+        self.verify_n(text, 1)
+    }
+
+    /// Returns true if the last `count` characters are the check digits of the others.
+    pub fn verify_n(&self, text: &str, count: usize) -> bool {
+        let split = match count {
+            0 => text.len(),
+            _ => match text.char_indices().nth_back(count - 1) {
+                Some((index, _)) => index,
+                None => return false,
+            },
+        };
+
+        let (base, digits) = text.split_at(split);
+        self.compute_n(base, count)
+            .is_ok_and(|computed| computed.iter().map(u32::to_string).collect::<String>() == digits)
+    }
+}
+
+fn sum_digits(mut number: u64) -> u64 {
+    let mut sum = 0;
+    while number > 0 {
+        sum += number % 10;
+        number /= 10;
+    }
+    sum
+}
+
+#[cfg(test)]
+mod check_digit_tests {
+    use super::*;
+
+    // cargo test -- --show-output check_digit_tests
+
+    #[test]
+    fn mod11() {
+        const CPF: CheckDigit = CheckDigit::mod11(&[2, 3, 4, 5, 6, 7, 8, 9, 10, 11]);
+        assert_eq!(CPF.append("529982247", 2), Ok("52998224725".to_string()));
+        assert!(CPF.verify_n("52998224725", 2));
+        assert!(!CPF.verify_n("52998224752", 2));
+
+        const CNPJ: CheckDigit = CheckDigit::MOD11.with_values(CharValues::AsciiMinus48);
+        assert_eq!(CNPJ.compute_n("12ABC34501DE", 2), Ok(vec![3, 5]));
+        assert_eq!(CNPJ.compute_n("112223330001", 2), Ok(vec![8, 1]));
+        assert_eq!(
+            CNPJ.compute("12abc"),
+            Err(CheckDigitError::InvalidCharacter('a'))
+        );
+        assert_eq!(
+            CheckDigit::MOD11.compute("12A"),
+            Err(CheckDigitError::InvalidCharacter('A'))
+        );
+    }
+
+    #[test]
+    fn mod10() {
+        // Luhn
+        assert!(CheckDigit::MOD10.verify("79927398713"));
+        assert!(!CheckDigit::MOD10.verify("79927398710"));
+
+        // Fields of a boleto digitable line
+        assert_eq!(CheckDigit::MOD10.compute("001905009"), Ok(5));
+        assert_eq!(CheckDigit::MOD10.compute("4014481606"), Ok(9));
+        assert_eq!(CheckDigit::MOD10.compute("0680935031"), Ok(4));
+    }
+
+    #[test]
+    fn rules_and_errors() {
+        const REMAINDER: CheckDigit =
+            CheckDigit::mod11(&[1, 2, 3]).with_rule(DigitRule::Remainder { overflow: 0 });
+        // 1*1 + 1*2 + 1*3 = 6
+        assert_eq!(REMAINDER.compute("111"), Ok(6));
+
+        const CUSTOM: CheckDigit = CheckDigit::new(7, &[1])
+            .with_rule(DigitRule::Custom(|remainder| remainder * 2))
+            .with_values(CharValues::Custom(|ch| {
+                ch.is_ascii_lowercase().then_some(1)
+            }));
+        assert_eq!(CUSTOM.compute("abc"), Ok(6));
+
+        assert_eq!(CheckDigit::MOD11.compute(""), Err(CheckDigitError::Empty));
+        assert!(!CheckDigit::MOD11.verify("7"));
+        assert!(!CheckDigit::MOD11.verify(""));
+        assert!(!CheckDigit::MOD11.verify("12é"));
+    }
+}
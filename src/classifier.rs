@@ -0,0 +1,469 @@
+use crate::{constants::OUTRAS_RECEITAS, open_file};
+use regex::{Regex, RegexBuilder, RegexSet, RegexSetBuilder};
+use std::{fmt, io::Read, path::Path};
+
+/// Errors returned when loading or compiling rule sets.
+#[derive(Debug)]
+pub enum ClassifierError {
+    /// Failure reading the rules file.
+    Io(std::io::Error),
+    /// Syntax error in the rules file, at the line number (starting at 1).
+    Syntax { line_number: usize, message: String },
+    /// A rule without `label` or `pattern`.
+    MissingField { rule: usize, field: &'static str },
+    /// A pattern or exclusion pattern that is not a valid regex.
+    InvalidPattern { label: String, error: regex::Error },
+}
+
+impl fmt::Display for ClassifierError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClassifierError::Io(error) => write!(f, "failed to read rules: {error}"),
+            ClassifierError::Syntax {
+                line_number,
+                message,
+            } => write!(f, "line {line_number}: {message}"),
+            ClassifierError::MissingField { rule, field } => {
+                write!(f, "rule {rule} without {field:?}")
+            }
+            ClassifierError::InvalidPattern { label, error } => {
+                write!(f, "invalid pattern in rule {label:?}: {error}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ClassifierError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ClassifierError::Io(error) => Some(error),
+            ClassifierError::InvalidPattern { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for ClassifierError {
+    fn from(error: std::io::Error) -> Self {
+        ClassifierError::Io(error)
+    }
+}
+
+/// A named classification rule.
+///
+/// Patterns are case-insensitive; use `(?-i)` to change it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassificationRule {
+    pub label: String,
+    pub pattern: String,
+    /// Descriptions matching this pattern are not flagged by the rule.
+    pub exclude: Option<String>,
+    /// Rules with higher priority come first in the results.
+    pub priority: i64,
+}
+
+impl ClassificationRule {
+    /// Rule with no exclusion pattern and priority 0.
+    pub fn new(label: &str, pattern: &str) -> Self {
+        ClassificationRule {
+            label: label.to_string(),
+            pattern: pattern.to_string(),
+            exclude: None,
+            priority: 0,
+        }
+    }
+}
+
+/// A rule that matched a description, and the text that triggered it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleMatch<'r, 't> {
+    pub rule: &'r ClassificationRule,
+    pub text: &'t str,
+}
+
+/**
+Classifier of ledger account descriptions by named rules.
+
+All patterns are compiled into a single `RegexSet`, and [`AccountClassifier::classify`]
+returns which rules matched, sorted by priority, with the matched text.
+
+Rule sets are written in a subset of TOML:
+```toml
+name = "outras receitas"
+
+[[rule]]
+label = "Receitas Financeiras"
+pattern = 'Rec.*Financ'
+exclude = 'Aplica'  # optional
+priority = 10       # optional, default 0
+```
+
+Example:
+```
+    use claudiofsr_lib::AccountClassifier;
+
+    let classifier = AccountClassifier::outras_receitas();
+    let matches = classifier.classify("Juros s/ Receitas Financeiras");
+    let labels: Vec<&str> = matches.iter().map(|m| m.rule.label.as_str()).collect();
+
+    assert_eq!(labels, ["Receitas Financeiras", "Juros sobre Capital Próprio"]);
+    assert_eq!(matches[0].text, "Receitas Financ");
+    assert!(!classifier.is_match("Receita de Vendas"));
+
+    let rules = r#"
+        name = "clientes"
+
+        [[rule]]
+        label = "Aluguel"
+        pattern = 'Alug'
+        exclude = 'Máquinas'
+    "#;
+
+    let classifier = AccountClassifier::from_toml_str(rules).unwrap();
+    assert_eq!(classifier.name(), "clientes");
+    assert!(classifier.is_match("ALUGUEL DE IMÓVEIS"));
+    assert!(!classifier.is_match("Aluguel de máquinas"));
+```
+*/
+#[derive(Debug, Clone)]
+pub struct AccountClassifier {
+    name: String,
+    rules: Vec<ClassificationRule>,
+    set: RegexSet,
+    patterns: Vec<Regex>,
+    excludes: Vec<Option<Regex>>,
+}
+
+impl AccountClassifier {
+    /// Compile a rule set.
+    pub fn new(name: &str, rules: Vec<ClassificationRule>) -> Result<Self, ClassifierError> {
+        let compile = |rule: &ClassificationRule, pattern: &str| {
+            RegexBuilder::new(pattern)
+                .case_insensitive(true)
+                .build()
+                .map_err(|error| ClassifierError::InvalidPattern {
+                    label: rule.label.clone(),
+                    error,
+                })
+        };
+
+        let mut patterns = Vec::with_capacity(rules.len());
+        let mut excludes = Vec::with_capacity(rules.len());
+
+        for rule in &rules {
+            patterns.push(compile(rule, &rule.pattern)?);
+            excludes.push(match &rule.exclude {
+                Some(exclude) => Some(compile(rule, exclude)?),
+                None => None,
+            });
+        }
+
+        // Each pattern is valid, so the set is too.
+        let set = RegexSetBuilder::new(rules.iter().map(|rule| &rule.pattern))
+            .case_insensitive(true)
+            .build()
+            .map_err(|error| ClassifierError::InvalidPattern {
+                label: name.to_string(),
+                error,
+            })?;
+
+        Ok(AccountClassifier {
+            name: name.to_string(),
+            rules,
+            set,
+            patterns,
+            excludes,
+        })
+    }
+
+    /// The default rule set: the "other revenues" of [`crate::OUTRAS_RECEITAS_REGEX`].
+    pub fn outras_receitas() -> Self {
+        let rules = OUTRAS_RECEITAS
+            .iter()
+            .map(|(label, pattern)| ClassificationRule::new(label, pattern))
+            .collect();
+
+        AccountClassifier::new("outras receitas", rules).expect("default rules must be valid")
+    }
+
+    /// Load a rule set from a file in the TOML subset described above.
+    pub fn from_file<P>(path: P) -> Result<Self, ClassifierError>
+    where
+        P: AsRef<Path>,
+    {
+        let mut text = String::new();
+        open_file(path)?.read_to_string(&mut text)?;
+        AccountClassifier::from_toml_str(&text)
+    }
+
+    /// Load a rule set from text in the TOML subset described above.
+    pub fn from_toml_str(text: &str) -> Result<Self, ClassifierError> {
+        let (name, rules) = parse_rules(text)?;
+        AccountClassifier::new(&name, rules)
+    }
+
+    /// Name of the rule set.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Rules in the order they were defined.
+    pub fn rules(&self) -> &[ClassificationRule] {
+        &self.rules
+    }
+
+    /// Rules matching the description, sorted by priority (highest first).
+    ///
+    /// Rules with the same priority keep the order in which they were defined.
+    pub fn classify<'t>(&self, description: &'t str) -> Vec<RuleMatch<'_, 't>> {
+        let mut matches: Vec<RuleMatch> = self
+            .set
+            .matches(description)
+            .into_iter()
+            .filter(|&index| match &self.excludes[index] {
+                Some(exclude) => !exclude.is_match(description),
+                None => true,
+            })
+            .filter_map(|index| {
+                let found = self.patterns[index].find(description)?;
+                Some(RuleMatch {
+                    rule: &self.rules[index],
+                    text: found.as_str(),
+                })
+            })
+            .collect();
+
+        matches.sort_by_key(|m| std::cmp::Reverse(m.rule.priority));
+        matches
+    }
+
+    /// Check if any rule matches the description.
+    pub fn is_match(&self, description: &str) -> bool {
+        !self.classify(description).is_empty()
+    }
+}
+
+/// Partially built rule, while reading its fields.
+#[derive(Default)]
+struct RuleFields {
+    label: Option<String>,
+    pattern: Option<String>,
+    exclude: Option<String>,
+    priority: Option<i64>,
+}
+
+/// A value of the TOML subset.
+enum Value {
+    Text(String),
+    Integer(i64),
+}
+
+/// Read the name and the rules of a rule set.
+fn parse_rules(text: &str) -> Result<(String, Vec<ClassificationRule>), ClassifierError> {
+    let mut name: Option<String> = None;
+    let mut fields: Vec<RuleFields> = Vec::new();
+
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        let syntax = |message: String| ClassifierError::Syntax {
+            line_number,
+            message,
+        };
+
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(header) = line.strip_prefix("[[") {
+            match strip_comment(header).trim() {
+                "rule]]" => fields.push(RuleFields::default()),
+                _ => return Err(syntax(format!("unknown table: {line}"))),
+            }
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| syntax(format!("expected key = value: {line}")))?;
+        let key = key.trim();
+        let value = parse_value(value.trim()).map_err(syntax)?;
+
+        let duplicate = || syntax(format!("duplicate key: {key}"));
+        let set_text = |slot: &mut Option<String>, value: Value| match (slot.is_some(), value) {
+            (true, _) => Err(duplicate()),
+            (false, Value::Text(text)) => {
+                *slot = Some(text);
+                Ok(())
+            }
+            (false, Value::Integer(_)) => Err(syntax(format!("{key} must be a string"))),
+        };
+
+        match (fields.last_mut(), key) {
+            (None, "name") => set_text(&mut name, value)?,
+            (Some(rule), "label") => set_text(&mut rule.label, value)?,
+            (Some(rule), "pattern") => set_text(&mut rule.pattern, value)?,
+            (Some(rule), "exclude") => set_text(&mut rule.exclude, value)?,
+            (Some(rule), "priority") => match (rule.priority, value) {
+                (Some(_), _) => return Err(duplicate()),
+                (None, Value::Integer(priority)) => rule.priority = Some(priority),
+                (None, Value::Text(_)) => {
+                    return Err(syntax("priority must be an integer".to_string()))
+                }
+            },
+            _ => return Err(syntax(format!("unknown key: {key}"))),
+        }
+    }
+
+    let rules = fields
+        .into_iter()
+        .enumerate()
+        .map(|(index, rule)| {
+            let missing = |field| ClassifierError::MissingField {
+                rule: index + 1,
+                field,
+            };
+            Ok(ClassificationRule {
+                label: rule.label.ok_or_else(|| missing("label"))?,
+                pattern: rule.pattern.ok_or_else(|| missing("pattern"))?,
+                exclude: rule.exclude,
+                priority: rule.priority.unwrap_or_default(),
+            })
+        })
+        .collect::<Result<Vec<_>, ClassifierError>>()?;
+
+    Ok((name.unwrap_or_default(), rules))
+}
+
+/// Parse a basic string ("..."), a literal string ('...') or an integer,
+/// followed by an optional comment.
+fn parse_value(value: &str) -> Result<Value, String> {
+    let mut chars = value.chars();
+
+    let (text, rest) = match chars.next() {
+        Some('\'') => {
+            let end = chars
+                .as_str()
+                .find('\'')
+                .ok_or_else(|| format!("unterminated string: {value}"))?;
+            let text = &chars.as_str()[..end];
+            (text.to_string(), &chars.as_str()[end + 1..])
+        }
+        Some('"') => {
+            let mut text = String::new();
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') => match chars.next() {
+                        Some('"') => text.push('"'),
+                        Some('\\') => text.push('\\'),
+                        Some('n') => text.push('\n'),
+                        Some('t') => text.push('\t'),
+                        other => return Err(format!("invalid escape: \\{}", other.unwrap_or(' '))),
+                    },
+                    Some(c) => text.push(c),
+                    None => return Err(format!("unterminated string: {value}")),
+                }
+            }
+            (text, chars.as_str())
+        }
+        _ => {
+            let number = strip_comment(value).trim();
+            return number
+                .parse::<i64>()
+                .map(Value::Integer)
+                .map_err(|_| format!("invalid value: {value}"));
+        }
+    };
+
+    if !strip_comment(rest).trim().is_empty() {
+        return Err(format!("unexpected text after string: {rest}"));
+    }
+
+    Ok(Value::Text(text))
+}
+
+/// Remove the text from the first `#`: only for text without strings,
+/// like table headers, integers and what follows a closing quote.
+fn strip_comment(text: &str) -> &str {
+    text.split_once('#').map_or(text, |(before, _)| before)
+}
+
+#[cfg(test)]
+mod classifier_tests {
+    use super::*;
+    use crate::OUTRAS_RECEITAS_REGEX;
+
+    // cargo test -- --show-output classifier_tests
+
+    #[test]
+    fn default_rules_agree_with_pattern() {
+        let classifier = AccountClassifier::outras_receitas();
+
+        let lines = [
+            "foo bar",
+            "Atualização Monetária",
+            "Outras Receitas Operacionais",
+            "Juros sobre Capital Próprio",
+            "Descontos",
+            "VARIACAO CAMBIAL ATIVA",
+            "Operações de hedge",
+        ];
+
+        for line in lines {
+            assert_eq!(
+                classifier.is_match(line),
+                OUTRAS_RECEITAS_REGEX.is_match(line),
+                "{line}"
+            );
+        }
+    }
+
+    #[test]
+    fn rules_from_toml() {
+        let text = r#"
+# Regras do cliente
+name = "cliente \"A\""
+
+[[rule]]
+label = "Juros"
+pattern = "Juro"
+
+[[rule]]
+label = "Juros sobre Capital Próprio"  # mais específica
+pattern = 'Juro.*Capital\s*Pr'
+priority = 10
+"#;
+        let classifier = AccountClassifier::from_toml_str(text).unwrap();
+        assert_eq!(classifier.name(), "cliente \"A\"");
+        assert_eq!(classifier.rules().len(), 2);
+
+        let matches = classifier.classify("JUROS S/ CAPITAL PROPRIO");
+        let labels: Vec<&str> = matches.iter().map(|m| m.rule.label.as_str()).collect();
+        assert_eq!(labels, ["Juros sobre Capital Próprio", "Juros"]);
+        assert_eq!(matches[0].text, "JUROS S/ CAPITAL PR");
+    }
+
+    #[test]
+    fn invalid_rules() {
+        let error = AccountClassifier::from_toml_str("[[rule]]\nlabel = 'A'\n").unwrap_err();
+        assert!(matches!(
+            error,
+            ClassifierError::MissingField {
+                rule: 1,
+                field: "pattern"
+            }
+        ));
+
+        let error = AccountClassifier::from_toml_str("[[rule]]\nlabel = 'A\n").unwrap_err();
+        assert!(matches!(
+            error,
+            ClassifierError::Syntax { line_number: 2, .. }
+        ));
+
+        let error =
+            AccountClassifier::from_toml_str("[[rule]]\nlabel = 'A'\npattern = '('\n").unwrap_err();
+        assert!(matches!(error, ClassifierError::InvalidPattern { .. }));
+    }
+}
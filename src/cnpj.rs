@@ -0,0 +1,282 @@
+use crate::{CharValues, CheckDigit};
+use std::{fmt, str::FromStr};
+
+/// Number of characters in a CNPJ, without the mask.
+const CNPJ_LEN: usize = 14;
+
+/// Mod-11 with weights 2 to 9 from right to left, letters valued as ASCII code minus 48.
+///
+/// Also the check digit of the access keys, whose emitter field can be an alphanumeric CNPJ.
+pub(crate) const CNPJ_CHECK_DIGIT: CheckDigit =
+    CheckDigit::MOD11.with_values(CharValues::AsciiMinus48);
+
+/// Errors returned when parsing a CNPJ.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CnpjError {
+    /// The number of characters, after removing the mask, is not 14.
+    InvalidLength(usize),
+    /// A character other than `0-9`, `A-Z` or the mask (`.`, `/`, `-`) was found.
+    ///
+    /// The check digits (last two positions) must always be numeric.
+    InvalidCharacter(char),
+    /// All characters are the same, like "00.000.000/0000-00".
+    RepeatedSequence,
+    /// The check digits do not match the calculated ones.
+    InvalidCheckDigits { expected: String, found: String },
+}
+
+impl fmt::Display for CnpjError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CnpjError::InvalidLength(len) => {
+                write!(f, "CNPJ must have {CNPJ_LEN} characters, found {len}")
+            }
+            CnpjError::InvalidCharacter(ch) => write!(f, "invalid character in CNPJ: {ch:?}"),
+            CnpjError::RepeatedSequence => write!(f, "CNPJ with all characters repeated"),
+            CnpjError::InvalidCheckDigits { expected, found } => {
+                write!(
+                    f,
+                    "invalid CNPJ check digits: expected {expected}, found {found}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for CnpjError {}
+
+/**
+Cadastro Nacional da Pessoa Jurídica (CNPJ) with verified check digits.
+
+Accepts the numeric CNPJ and the alphanumeric CNPJ issued by Receita Federal
+from July 2026 on: the first 12 characters (`raiz` + `filial`) may contain
+uppercase letters, and each character is valued as its ASCII code minus 48
+in the mod-11 calculation.
+
+Example:
+```
+    use claudiofsr_lib::Cnpj;
+
+    let cnpj: Cnpj = "12.ABC.345/01DE-35".parse().unwrap();
+    assert_eq!(cnpj.as_str(), "12ABC34501DE35");
+    assert_eq!(cnpj.raiz(), "12ABC345");
+    assert_eq!(cnpj.filial(), "01DE");
+    assert_eq!(cnpj.to_string(), "12.ABC.345/01DE-35");
+
+    let cnpj = Cnpj::parse("11222333000181").unwrap();
+    assert!(cnpj.is_matriz());
+    assert_eq!(cnpj.to_string(), "11.222.333/0001-81");
+
+    assert!(Cnpj::parse("12ABC678901234").is_err());
+```
+
+<https://www.gov.br/receitafederal/pt-br/acesso-a-informacao/acoes-e-programas/programas-e-atividades/cnpj-alfanumerico>
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Cnpj([u8; CNPJ_LEN]);
+
+impl Cnpj {
+    /// Parse a CNPJ, masked ("12.ABC.345/01DE-35") or unmasked ("12ABC34501DE35").
+    ///
+    /// Lowercase letters are converted to uppercase.
+    pub fn parse(cnpj: &str) -> Result<Self, CnpjError> {
+        let chars = normalize(cnpj)?;
+
+        if chars.len() != CNPJ_LEN {
+            return Err(CnpjError::InvalidLength(chars.len()));
+        }
+
+        if let Some(&ch) = chars[12..].iter().find(|b| !b.is_ascii_digit()) {
+            return Err(CnpjError::InvalidCharacter(ch as char));
+        }
+
+        if chars.iter().all(|&b| b == chars[0]) {
+            return Err(CnpjError::RepeatedSequence);
+        }
+
+        let mut bytes = [0; CNPJ_LEN];
+        bytes.copy_from_slice(&chars);
+
+        let expected = check_digits(&bytes[..12]);
+        if expected != bytes[12..] {
+            return Err(CnpjError::InvalidCheckDigits {
+                expected: String::from_utf8_lossy(&expected).to_string(),
+                found: String::from_utf8_lossy(&bytes[12..]).to_string(),
+            });
+        }
+
+        Ok(Cnpj(bytes))
+    }
+
+    /**
+    Build a CNPJ from its first 12 characters (`raiz` + `filial`),
+    computing the check digits.
+
+    ```
+        use claudiofsr_lib::Cnpj;
+
+        let cnpj = Cnpj::from_base("12ABC34501DE").unwrap();
+        assert_eq!(cnpj.check_digits(), "35");
+    ```
+    */
+    pub fn from_base(base: &str) -> Result<Self, CnpjError> {
+        let chars = normalize(base)?;
+
+        if chars.len() != 12 {
+            return Err(CnpjError::InvalidLength(chars.len()));
+        }
+
+        let mut bytes = [0; CNPJ_LEN];
+        bytes[..12].copy_from_slice(&chars);
+        bytes[12..].copy_from_slice(&check_digits(&chars));
+
+        if bytes.iter().all(|&b| b == bytes[0]) {
+            return Err(CnpjError::RepeatedSequence);
+        }
+
+        Ok(Cnpj(bytes))
+    }
+
+    /// The 14 characters without the mask.
+    pub fn as_str(&self) -> &str {
+        // Only ASCII alphanumeric bytes are stored.
+        std::str::from_utf8(&self.0).unwrap_or_default()
+    }
+
+    /// Root (raiz): the first 8 characters, shared by all establishments of the company.
+    pub fn raiz(&self) -> &str {
+        &self.as_str()[..8]
+    }
+
+    /// Branch (filial): the 4 characters that identify the establishment.
+    pub fn filial(&self) -> &str {
+        &self.as_str()[8..12]
+    }
+
+    /// The two check digits.
+    pub fn check_digits(&self) -> &str {
+        &self.as_str()[12..]
+    }
+
+    /// Returns true if it is the head office (filial "0001").
+    pub fn is_matriz(&self) -> bool {
+        self.filial() == "0001"
+    }
+
+    /// Returns true if the CNPJ contains letters (alphanumeric CNPJ).
+    pub fn is_alphanumeric(&self) -> bool {
+        self.0.iter().any(u8::is_ascii_alphabetic)
+    }
+}
+
+impl FromStr for Cnpj {
+    type Err = CnpjError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Cnpj::parse(s)
+    }
+}
+
+impl fmt::Display for Cnpj {
+    /// Format with the mask: "12.ABC.345/01DE-35".
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = self.as_str();
+        write!(
+            f,
+            "{}.{}.{}/{}-{}",
+            &s[0..2],
+            &s[2..5],
+            &s[5..8],
+            &s[8..12],
+            &s[12..]
+        )
+    }
+}
+
+/// Remove the mask and convert to uppercase ASCII.
+fn normalize(cnpj: &str) -> Result<Vec<u8>, CnpjError> {
+    let mut chars = Vec::with_capacity(CNPJ_LEN);
+
+    for ch in cnpj.trim().chars() {
+        match ch {
+            '.' | '/' | '-' => continue,
+            c if c.is_ascii_alphanumeric() => chars.push(c.to_ascii_uppercase() as u8),
+            c => return Err(CnpjError::InvalidCharacter(c)),
+        }
+    }
+
+    Ok(chars)
+}
+
+/// Compute the two check digits from the first 12 characters.
+fn check_digits(base: &[u8]) -> [u8; 2] {
+    let digits = CNPJ_CHECK_DIGIT
+        .compute_n(&String::from_utf8_lossy(base), 2)
+        .expect("normalized to 0-9 and A-Z");
+
+    [digits[0] as u8 + b'0', digits[1] as u8 + b'0']
+}
+
+#[cfg(test)]
+mod cnpj_tests {
+    use super::*;
+
+    // cargo test -- --show-output cnpj_tests
+
+    #[test]
+    fn parse_numeric_cnpj() {
+        for input in ["11.222.333/0001-81", "11222333000181", " 11222333000181\n"] {
+            let cnpj = Cnpj::parse(input).unwrap();
+            assert_eq!(cnpj.as_str(), "11222333000181");
+            assert_eq!(cnpj.raiz(), "11222333");
+            assert_eq!(cnpj.filial(), "0001");
+            assert!(!cnpj.is_alphanumeric());
+        }
+    }
+
+    #[test]
+    fn parse_alphanumeric_cnpj() {
+        let cnpj: Cnpj = "12.abc.345/01de-35".parse().unwrap();
+        assert_eq!(cnpj.as_str(), "12ABC34501DE35");
+        assert_eq!(cnpj.to_string(), "12.ABC.345/01DE-35");
+        assert!(cnpj.is_alphanumeric());
+        assert!(!cnpj.is_matriz());
+    }
+
+    #[test]
+    fn invalid_cnpj() {
+        assert_eq!(
+            Cnpj::parse("12ABC678901234"),
+            Err(CnpjError::InvalidCheckDigits {
+                expected: "52".to_string(),
+                found: "34".to_string()
+            })
+        );
+        assert_eq!(
+            Cnpj::parse("1122233300018"),
+            Err(CnpjError::InvalidLength(13))
+        );
+        assert_eq!(
+            Cnpj::parse("11.222.333/0001#81"),
+            Err(CnpjError::InvalidCharacter('#'))
+        );
+        assert_eq!(
+            Cnpj::parse("12ABC34501DE3A"),
+            Err(CnpjError::InvalidCharacter('A'))
+        );
+        assert_eq!(
+            Cnpj::parse("00.000.000/0000-00"),
+            Err(CnpjError::RepeatedSequence)
+        );
+    }
+
+    #[test]
+    fn build_from_base() {
+        let cnpj = Cnpj::from_base("11.222.333/0001").unwrap();
+        assert_eq!(cnpj.to_string(), "11.222.333/0001-81");
+        assert_eq!(
+            Cnpj::from_base("1122233300"),
+            Err(CnpjError::InvalidLength(10))
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::{Cfop, CstPisCofins, NaturezaBaseCalculo};
 use regex::Regex;
 use std::{fmt::Display, sync::LazyLock};
 
@@ -121,6 +122,8 @@ impl<I: IntoIterator<Item=T>, T: Deref> SliceExtension for I {
 */
 
 /// Valores de 1 a 99
+///
+/// Includes numbers that are not in the official table, see [`CstPisCofins::ALL`].
 pub const CST_ALL: [u16; 99] = {
     let mut output = [0; 99];
     let mut index: usize = 0;
@@ -146,42 +149,75 @@ pub const BASE_CALC_SOMA: [u16; 99] = {
     output
 };
 
-pub const CST_CREDITO: [u16; 14] = [50, 51, 52, 53, 54, 55, 56, 60, 61, 62, 63, 64, 65, 66];
+/// Codes of the CSTs that satisfy the `CstPisCofins` predicate, in ascending order.
+macro_rules! cst_codes {
+    ($size:literal, $predicate:ident) => {{
+        let mut output = [0; $size];
+        let mut count: usize = 0;
+        let mut index: usize = 0;
+
+        while index < CstPisCofins::ALL.len() {
+            let cst = CstPisCofins::ALL[index];
+            if cst.$predicate() {
+                output[count] = cst.code();
+                count += 1;
+            }
+            index += 1;
+        }
+
+        output
+    }};
+}
+
+/// Valores de 50 a 56 e de 60 a 66: [`CstPisCofins::is_credito`]
+pub const CST_CREDITO: [u16; 14] = cst_codes!(14, is_credito);
 
-pub const CST_CREDITO_BASICO: [u16; 7] = [50, 51, 52, 53, 54, 55, 56];
+/// Valores de 50 a 56: [`CstPisCofins::is_credito_basico`]
+pub const CST_CREDITO_BASICO: [u16; 7] = cst_codes!(7, is_credito_basico);
 
-pub const CST_CREDITO_PRESUMIDO: [u16; 7] = [60, 61, 62, 63, 64, 65, 66];
+/// Valores de 60 a 66: [`CstPisCofins::is_credito_presumido`]
+pub const CST_CREDITO_PRESUMIDO: [u16; 7] = cst_codes!(7, is_credito_presumido);
 
-/// Valores de 1 a 9 e 49
-pub const CST_RECEITA_BRUTA: [u16; 10] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 49];
+/// Valores de 1 a 9 e 49: [`CstPisCofins::is_receita_bruta`]
+pub const CST_RECEITA_BRUTA: [u16; 10] = cst_codes!(10, is_receita_bruta);
 
-pub const CSTS_NAO_TRIBUTADOS: [u16; 6] = [4, 6, 7, 8, 9, 49];
+/// Valores 4, 6, 7, 8, 9 e 49: [`CstPisCofins::is_nao_tributado`]
+pub const CSTS_NAO_TRIBUTADOS: [u16; 6] = cst_codes!(6, is_nao_tributado);
 
-/// Valores de 1 a 18
+/// Valores de 1 a 18: [`NaturezaBaseCalculo::ALL`]
 pub const CODIGO_DA_NATUREZA_BC: [u16; 18] = {
-    // Initialize an array of size 18 with all elements set to 0.
     let mut output = [0; 18];
-
-    // Initialize an index variable starting at 0.
     let mut index: usize = 0;
 
-    // Loop through the array and assign values from 1 to 18.
     while index < output.len() {
-        // Assign the current index + 1 to the corresponding position in the array.
-        output[index] = (index + 1) as u16;
-
-        // Increment the index by 1.
+        output[index] = NaturezaBaseCalculo::ALL[index].code();
         index += 1;
     }
 
-    // Return the populated array.
     output
 };
 
-pub const CFOP_VENDA_DE_IMOBILIZADO: [u16; 3] = [5551, 6551, 7551];
+/// CFOP de venda de bem do ativo imobilizado: [`Cfop::is_venda_de_imobilizado`].
+pub const CFOP_VENDA_DE_IMOBILIZADO: [u16; 3] = {
+    let mut output = [0; 3];
+    let mut index: usize = 0;
+    let mut code: u16 = 1000;
+
+    while code <= 7999 {
+        if let Some(cfop) = Cfop::from_code(code) {
+            if cfop.is_venda_de_imobilizado() {
+                output[index] = code;
+                index += 1;
+            }
+        }
+        code += 1;
+    }
+
+    output
+};
 
 /**
-CFOP de Exportação:
+CFOP de Exportação: [`Cfop::is_exportacao`].
 ```ignore
 Grupo 7:
 
@@ -207,48 +243,65 @@ Fim específico de exportação:
 */
 pub const CFOP_DE_EXPORTACAO: [u16; 1026] = {
     let mut output = [0; 1026];
-
-    let fim_de_exportacao: [u16; 26] = [
-        1500, 1501, 1503, 1504, 1505, 1506, 2500, 2501, 2503, 2504, 2505, 2506, 3500, 3503, 5500,
-        5501, 5502, 5503, 5504, 5505, 6500, 6501, 6502, 6503, 6504, 6505,
-    ];
-
     let mut index: usize = 0;
-
-    while index < output.len() {
-        if index < 26 {
-            output[index] = fim_de_exportacao[index];
-        } else {
-            output[index] = (index - 26) as u16 + 7000;
+    let mut code: u16 = 1000;
+
+    // Ascending order: the codes with specific purpose of export come before 7000.
+    while code <= 7999 {
+        if let Some(cfop) = Cfop::from_group(code) {
+            if cfop.is_exportacao() {
+                output[index] = code;
+                index += 1;
+            }
         }
-
-        index += 1;
+        code += 1;
     }
 
     output
 };
 
-// Regex, flags:
-// x: verbose mode, ignores whitespace and allow line comments (starting with `#`)
-// i: case-insensitive: letters match both upper and lower case
-pub const PATTERN: &str = r#"(?xi)
-# Esta é uma lista com possíveis Receitas Não Operacionais (outras receitas)
-# a depender das atividades que constituam objeto da empresa:
-    Atualiz.*Monet|   # Atualização Monetária
-    Vend.*Imobiliz|   # Venda de Imobilizado
-    Var.*Camb|        # Variação Cambial
-    Desc.*Financ|     # Descontos Financeiros
-    Desc.*Obtido|     # Descontos Obtidos
-    Desp.*N.*Oper|    # Despesas Não Operacionais
-    Rec.*Financ|      # Receitas Financeiras
-    Rec.*N.*Oper|     # Receitas Não Operacionais
-    Outras?\s*Rec|    # Outras Receitas (mesmo que Receitas Não Operacionais)
-    Outras?\s*Desp|   # Outras Despesas
-    Juro|             # Juros sobre Capital Próprio
-    Selic|            # Selic é a taxa básica de juros da economia
-    Hedge
-"#;
+/// Define [`PATTERN`] and [`OUTRAS_RECEITAS`] from the same list of (label, regex) pairs:
+/// each pair is one alternative of the regex, commented with its label.
+macro_rules! outras_receitas {
+    (($label:literal, $pattern:literal) $(, ($labels:literal, $patterns:literal))* $(,)?) => {
+        // Regex, flags:
+        // x: verbose mode, ignores whitespace and allow line comments (starting with `#`)
+        // i: case-insensitive: letters match both upper and lower case
+        pub const PATTERN: &str = concat!(
+            "(?xi)\n",
+            "# Esta é uma lista com possíveis Receitas Não Operacionais (outras receitas)\n",
+            "# a depender das atividades que constituam objeto da empresa:\n",
+            "    ", $pattern, "  # ", $label, "\n",
+            $("  | ", $patterns, "  # ", $labels, "\n",)*
+        );
+
+        /// Rules of [`crate::AccountClassifier::outras_receitas`]: the alternatives of [`PATTERN`].
+        pub(crate) const OUTRAS_RECEITAS: &[(&str, &str)] = &[
+            ($label, $pattern),
+            $(($labels, $patterns),)*
+        ];
+    };
+}
 
+outras_receitas![
+    ("Atualização Monetária", r"Atualiz.*Monet"),
+    ("Venda de Imobilizado", r"Vend.*Imobiliz"),
+    ("Variação Cambial", r"Var.*Camb"),
+    ("Descontos Financeiros", r"Desc.*Financ"),
+    ("Descontos Obtidos", r"Desc.*Obtido"),
+    ("Despesas Não Operacionais", r"Desp.*N.*Oper"),
+    ("Receitas Financeiras", r"Rec.*Financ"),
+    ("Receitas Não Operacionais", r"Rec.*N.*Oper"),
+    // Outras Receitas: mesmo que Receitas Não Operacionais
+    ("Outras Receitas", r"Outras?\s*Rec"),
+    ("Outras Despesas", r"Outras?\s*Desp"),
+    ("Juros sobre Capital Próprio", r"Juro"),
+    // Selic é a taxa básica de juros da economia
+    ("Selic", r"Selic"),
+    ("Hedge", r"Hedge"),
+];
+
+/// To know which keyword matched, use [`crate::AccountClassifier::outras_receitas`].
 pub static OUTRAS_RECEITAS_REGEX: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(PATTERN).expect("OUTRAS_RECEITAS_REGEX regex inválida!"));
 
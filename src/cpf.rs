@@ -0,0 +1,238 @@
+use crate::CheckDigit;
+use std::{fmt, str::FromStr};
+
+/// Number of digits in a CPF, without the mask.
+const CPF_LEN: usize = 11;
+
+/// Mod-11 with weights from 10 down to 2 for the first digit,
+/// and from 11 down to 2 for the second.
+const CPF_CHECK_DIGIT: CheckDigit = CheckDigit::mod11(&[2, 3, 4, 5, 6, 7, 8, 9, 10, 11]);
+
+/// Errors returned when parsing a CPF.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CpfError {
+    /// The number of digits, after removing the mask, is not 11.
+    InvalidLength(usize),
+    /// A character other than a digit or the mask (`.`, `-`) was found.
+    InvalidCharacter(char),
+    /// All digits are the same, like "111.111.111-11".
+    RepeatedSequence,
+    /// The check digits do not match the calculated ones.
+    InvalidCheckDigits { expected: String, found: String },
+}
+
+impl fmt::Display for CpfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CpfError::InvalidLength(len) => {
+                write!(f, "CPF must have {CPF_LEN} digits, found {len}")
+            }
+            CpfError::InvalidCharacter(ch) => write!(f, "invalid character in CPF: {ch:?}"),
+            CpfError::RepeatedSequence => write!(f, "CPF with all digits repeated"),
+            CpfError::InvalidCheckDigits { expected, found } => {
+                write!(
+                    f,
+                    "invalid CPF check digits: expected {expected}, found {found}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for CpfError {}
+
+/**
+Cadastro de Pessoas Físicas (CPF) with verified check digits.
+
+Example:
+```
+    use claudiofsr_lib::{Cpf, CpfError};
+
+    let cpf: Cpf = "529.982.247-25".parse().unwrap();
+    assert_eq!(cpf.as_str(), "52998224725");
+    assert_eq!(cpf.to_string(), "529.982.247-25");
+    assert_eq!(cpf.regiao_fiscal(), 7);
+    assert_eq!(cpf.regiao_fiscal_ufs(), ["ES", "RJ"]);
+
+    assert_eq!(Cpf::parse("111.111.111-11"), Err(CpfError::RepeatedSequence));
+    assert_eq!(Cpf::parse("123ABC78901"), Err(CpfError::InvalidCharacter('A')));
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Cpf([u8; CPF_LEN]);
+
+impl Cpf {
+    /// Parse a CPF, masked ("529.982.247-25") or unmasked ("52998224725").
+    pub fn parse(cpf: &str) -> Result<Self, CpfError> {
+        let digits = normalize(cpf)?;
+
+        if digits.len() != CPF_LEN {
+            return Err(CpfError::InvalidLength(digits.len()));
+        }
+
+        if digits.iter().all(|&b| b == digits[0]) {
+            return Err(CpfError::RepeatedSequence);
+        }
+
+        let mut bytes = [0; CPF_LEN];
+        bytes.copy_from_slice(&digits);
+
+        let expected = check_digits(&bytes[..9]);
+        if expected != bytes[9..] {
+            return Err(CpfError::InvalidCheckDigits {
+                expected: String::from_utf8_lossy(&expected).to_string(),
+                found: String::from_utf8_lossy(&bytes[9..]).to_string(),
+            });
+        }
+
+        Ok(Cpf(bytes))
+    }
+
+    /**
+    Build a CPF from its first 9 digits, computing the check digits.
+
+    ```
+        use claudiofsr_lib::Cpf;
+
+        let cpf = Cpf::from_base("123.456.789").unwrap();
+        assert_eq!(cpf.to_string(), "123.456.789-09");
+    ```
+    */
+    pub fn from_base(base: &str) -> Result<Self, CpfError> {
+        let digits = normalize(base)?;
+
+        if digits.len() != 9 {
+            return Err(CpfError::InvalidLength(digits.len()));
+        }
+
+        let mut bytes = [0; CPF_LEN];
+        bytes[..9].copy_from_slice(&digits);
+        bytes[9..].copy_from_slice(&check_digits(&digits));
+
+        if bytes.iter().all(|&b| b == bytes[0]) {
+            return Err(CpfError::RepeatedSequence);
+        }
+
+        Ok(Cpf(bytes))
+    }
+
+    /// The 11 digits without the mask.
+    pub fn as_str(&self) -> &str {
+        // Only ASCII digits are stored.
+        std::str::from_utf8(&self.0).unwrap_or_default()
+    }
+
+    /// The two check digits.
+    pub fn check_digits(&self) -> &str {
+        &self.as_str()[9..]
+    }
+
+    /// Fiscal region (região fiscal): the 9th digit, where the CPF was issued.
+    pub fn regiao_fiscal(&self) -> u8 {
+        self.0[8] - b'0'
+    }
+
+    /// States (UF) covered by the fiscal region of the CPF.
+    pub fn regiao_fiscal_ufs(&self) -> &'static [&'static str] {
+        match self.regiao_fiscal() {
+            0 => &["RS"],
+            1 => &["DF", "GO", "MS", "MT", "TO"],
+            2 => &["AC", "AM", "AP", "PA", "RO", "RR"],
+            3 => &["CE", "MA", "PI"],
+            4 => &["AL", "PB", "PE", "RN"],
+            5 => &["BA", "SE"],
+            6 => &["MG"],
+            7 => &["ES", "RJ"],
+            8 => &["SP"],
+            _ => &["PR", "SC"],
+        }
+    }
+}
+
+impl FromStr for Cpf {
+    type Err = CpfError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Cpf::parse(s)
+    }
+}
+
+impl fmt::Display for Cpf {
+    /// Format with the mask: "529.982.247-25".
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = self.as_str();
+        write!(f, "{}.{}.{}-{}", &s[0..3], &s[3..6], &s[6..9], &s[9..])
+    }
+}
+
+/// Remove the mask, keeping only the digits.
+fn normalize(cpf: &str) -> Result<Vec<u8>, CpfError> {
+    let mut digits = Vec::with_capacity(CPF_LEN);
+
+    for ch in cpf.trim().chars() {
+        match ch {
+            '.' | '-' => continue,
+            c if c.is_ascii_digit() => digits.push(c as u8),
+            c => return Err(CpfError::InvalidCharacter(c)),
+        }
+    }
+
+    Ok(digits)
+}
+
+/// Compute the two check digits from the first 9 digits.
+fn check_digits(base: &[u8]) -> [u8; 2] {
+    let mut values: Vec<u32> = base.iter().map(|&b| u32::from(b - b'0')).collect();
+
+    let dv1 = CPF_CHECK_DIGIT.compute_values(&values);
+    values.push(dv1);
+    let dv2 = CPF_CHECK_DIGIT.compute_values(&values);
+
+    [dv1 as u8 + b'0', dv2 as u8 + b'0']
+}
+
+#[cfg(test)]
+mod cpf_tests {
+    use super::*;
+
+    // cargo test -- --show-output cpf_tests
+
+    #[test]
+    fn parse_valid_cpf() {
+        for input in ["529.982.247-25", "52998224725", " 52998224725 "] {
+            let cpf = Cpf::parse(input).unwrap();
+            assert_eq!(cpf.as_str(), "52998224725");
+            assert_eq!(cpf.check_digits(), "25");
+        }
+
+        let cpf: Cpf = "111.444.777-35".parse().unwrap();
+        assert_eq!(cpf.regiao_fiscal(), 7);
+    }
+
+    #[test]
+    fn invalid_cpf() {
+        assert_eq!(Cpf::parse("5299822472"), Err(CpfError::InvalidLength(10)));
+        assert_eq!(
+            Cpf::parse("529/982/247-25"),
+            Err(CpfError::InvalidCharacter('/'))
+        );
+        assert_eq!(
+            Cpf::parse("000.000.000-00"),
+            Err(CpfError::RepeatedSequence)
+        );
+        assert_eq!(
+            Cpf::parse("529.982.247-52"),
+            Err(CpfError::InvalidCheckDigits {
+                expected: "25".to_string(),
+                found: "52".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn build_from_base() {
+        let cpf = Cpf::from_base("111444777").unwrap();
+        assert_eq!(cpf.to_string(), "111.444.777-35");
+        assert_eq!(Cpf::from_base("999999999"), Err(CpfError::RepeatedSequence));
+    }
+}
@@ -0,0 +1,333 @@
+use std::{fmt, str::FromStr};
+
+/// Errors returned when converting a code to [`CstPisCofins`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CstError {
+    /// The text is not a number with up to 2 digits.
+    InvalidFormat(String),
+    /// The number is not in the official CST table.
+    InvalidCode(u16),
+}
+
+impl fmt::Display for CstError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CstError::InvalidFormat(cst) => write!(f, "CST must have up to 2 digits: {cst:?}"),
+            CstError::InvalidCode(code) => write!(f, "invalid PIS/COFINS CST: {code}"),
+        }
+    }
+}
+
+impl std::error::Error for CstError {}
+
+/**
+Código de Situação Tributária (CST) of PIS/PASEP and COFINS.
+
+Official table 4.3.3 of EFD-Contribuições.
+
+Example:
+```
+    use claudiofsr_lib::CstPisCofins;
+
+    let cst = CstPisCofins::try_from(51).unwrap();
+    assert!(cst.is_credito());
+    assert!(cst.is_credito_basico());
+    assert!(!cst.is_receita_bruta());
+    assert_eq!(cst.to_string(), "51");
+
+    let cst: CstPisCofins = "06".parse().unwrap();
+    assert_eq!(cst, CstPisCofins::AliquotaZero);
+    assert!(cst.is_nao_tributado());
+    assert_eq!(cst.descricao(), "Operação Tributável a Alíquota Zero");
+
+    assert!(CstPisCofins::try_from(10).is_err());
+    assert_eq!(CstPisCofins::iter().count(), 33);
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[repr(u16)]
+pub enum CstPisCofins {
+    AliquotaBasica = 1,
+    AliquotaDiferenciada = 2,
+    AliquotaPorUnidade = 3,
+    MonofasicaRevendaAliquotaZero = 4,
+    SubstituicaoTributaria = 5,
+    AliquotaZero = 6,
+    Isenta = 7,
+    SemIncidencia = 8,
+    Suspensao = 9,
+    OutrasOperacoesDeSaida = 49,
+    CreditoReceitaTributada = 50,
+    CreditoReceitaNaoTributada = 51,
+    CreditoReceitaExportacao = 52,
+    CreditoReceitasTributadaENaoTributada = 53,
+    CreditoReceitasTributadaEExportacao = 54,
+    CreditoReceitasNaoTributadaEExportacao = 55,
+    CreditoReceitasTributadaNaoTributadaEExportacao = 56,
+    PresumidoReceitaTributada = 60,
+    PresumidoReceitaNaoTributada = 61,
+    PresumidoReceitaExportacao = 62,
+    PresumidoReceitasTributadaENaoTributada = 63,
+    PresumidoReceitasTributadaEExportacao = 64,
+    PresumidoReceitasNaoTributadaEExportacao = 65,
+    PresumidoReceitasTributadaNaoTributadaEExportacao = 66,
+    PresumidoOutrasOperacoes = 67,
+    AquisicaoSemDireitoACredito = 70,
+    AquisicaoComIsencao = 71,
+    AquisicaoComSuspensao = 72,
+    AquisicaoAliquotaZero = 73,
+    AquisicaoSemIncidencia = 74,
+    AquisicaoPorSubstituicaoTributaria = 75,
+    OutrasOperacoesDeEntrada = 98,
+    OutrasOperacoes = 99,
+}
+
+impl CstPisCofins {
+    /// All CSTs of the official table, in ascending order.
+    pub const ALL: [CstPisCofins; 33] = [
+        CstPisCofins::AliquotaBasica,
+        CstPisCofins::AliquotaDiferenciada,
+        CstPisCofins::AliquotaPorUnidade,
+        CstPisCofins::MonofasicaRevendaAliquotaZero,
+        CstPisCofins::SubstituicaoTributaria,
+        CstPisCofins::AliquotaZero,
+        CstPisCofins::Isenta,
+        CstPisCofins::SemIncidencia,
+        CstPisCofins::Suspensao,
+        CstPisCofins::OutrasOperacoesDeSaida,
+        CstPisCofins::CreditoReceitaTributada,
+        CstPisCofins::CreditoReceitaNaoTributada,
+        CstPisCofins::CreditoReceitaExportacao,
+        CstPisCofins::CreditoReceitasTributadaENaoTributada,
+        CstPisCofins::CreditoReceitasTributadaEExportacao,
+        CstPisCofins::CreditoReceitasNaoTributadaEExportacao,
+        CstPisCofins::CreditoReceitasTributadaNaoTributadaEExportacao,
+        CstPisCofins::PresumidoReceitaTributada,
+        CstPisCofins::PresumidoReceitaNaoTributada,
+        CstPisCofins::PresumidoReceitaExportacao,
+        CstPisCofins::PresumidoReceitasTributadaENaoTributada,
+        CstPisCofins::PresumidoReceitasTributadaEExportacao,
+        CstPisCofins::PresumidoReceitasNaoTributadaEExportacao,
+        CstPisCofins::PresumidoReceitasTributadaNaoTributadaEExportacao,
+        CstPisCofins::PresumidoOutrasOperacoes,
+        CstPisCofins::AquisicaoSemDireitoACredito,
+        CstPisCofins::AquisicaoComIsencao,
+        CstPisCofins::AquisicaoComSuspensao,
+        CstPisCofins::AquisicaoAliquotaZero,
+        CstPisCofins::AquisicaoSemIncidencia,
+        CstPisCofins::AquisicaoPorSubstituicaoTributaria,
+        CstPisCofins::OutrasOperacoesDeEntrada,
+        CstPisCofins::OutrasOperacoes,
+    ];
+
+    /// Iterate over all CSTs of the official table, in ascending order.
+    pub fn iter() -> impl Iterator<Item = CstPisCofins> {
+        CstPisCofins::ALL.into_iter()
+    }
+
+    /// Same as `CstPisCofins::try_from`, usable in constant expressions.
+    pub const fn from_code(code: u16) -> Option<Self> {
+        let mut index: usize = 0;
+
+        while index < CstPisCofins::ALL.len() {
+            if CstPisCofins::ALL[index] as u16 == code {
+                return Some(CstPisCofins::ALL[index]);
+            }
+            index += 1;
+        }
+
+        None
+    }
+
+    /// The numeric code.
+    pub const fn code(self) -> u16 {
+        self as u16
+    }
+
+    /// Credit: [`CST_CREDITO`](crate::CST_CREDITO), basic or presumed.
+    pub const fn is_credito(self) -> bool {
+        self.is_credito_basico() || self.is_credito_presumido()
+    }
+
+    /// Basic credit: [`CST_CREDITO_BASICO`](crate::CST_CREDITO_BASICO), 50 to 56.
+    pub const fn is_credito_basico(self) -> bool {
+        matches!(self.code(), 50..=56)
+    }
+
+    /// Presumed credit: [`CST_CREDITO_PRESUMIDO`](crate::CST_CREDITO_PRESUMIDO), 60 to 66.
+    ///
+    /// CST 67 (other operations) is not linked to a type of revenue and is not included.
+    pub const fn is_credito_presumido(self) -> bool {
+        matches!(self.code(), 60..=66)
+    }
+
+    /// Gross revenue: [`CST_RECEITA_BRUTA`](crate::CST_RECEITA_BRUTA), 1 to 9 and 49.
+    pub const fn is_receita_bruta(self) -> bool {
+        matches!(self.code(), 1..=9 | 49)
+    }
+
+    /// Non-taxed revenue: [`CSTS_NAO_TRIBUTADOS`](crate::CSTS_NAO_TRIBUTADOS).
+    pub const fn is_nao_tributado(self) -> bool {
+        matches!(self.code(), 4 | 6..=9 | 49)
+    }
+
+    /// Official description.
+    pub const fn descricao(self) -> &'static str {
+        match self {
+            CstPisCofins::AliquotaBasica => "Operação Tributável com Alíquota Básica",
+            CstPisCofins::AliquotaDiferenciada => "Operação Tributável com Alíquota Diferenciada",
+            CstPisCofins::AliquotaPorUnidade => {
+                "Operação Tributável com Alíquota por Unidade de Medida de Produto"
+            }
+            CstPisCofins::MonofasicaRevendaAliquotaZero => {
+                "Operação Tributável Monofásica - Revenda a Alíquota Zero"
+            }
+            CstPisCofins::SubstituicaoTributaria => {
+                "Operação Tributável por Substituição Tributária"
+            }
+            CstPisCofins::AliquotaZero => "Operação Tributável a Alíquota Zero",
+            CstPisCofins::Isenta => "Operação Isenta da Contribuição",
+            CstPisCofins::SemIncidencia => "Operação sem Incidência da Contribuição",
+            CstPisCofins::Suspensao => "Operação com Suspensão da Contribuição",
+            CstPisCofins::OutrasOperacoesDeSaida => "Outras Operações de Saída",
+            CstPisCofins::CreditoReceitaTributada => {
+                "Operação com Direito a Crédito - Vinculada Exclusivamente a Receita Tributada no Mercado Interno"
+            }
+            CstPisCofins::CreditoReceitaNaoTributada => {
+                "Operação com Direito a Crédito - Vinculada Exclusivamente a Receita Não Tributada no Mercado Interno"
+            }
+            CstPisCofins::CreditoReceitaExportacao => {
+                "Operação com Direito a Crédito - Vinculada Exclusivamente a Receita de Exportação"
+            }
+            CstPisCofins::CreditoReceitasTributadaENaoTributada => {
+                "Operação com Direito a Crédito - Vinculada a Receitas Tributadas e Não-Tributadas no Mercado Interno"
+            }
+            CstPisCofins::CreditoReceitasTributadaEExportacao => {
+                "Operação com Direito a Crédito - Vinculada a Receitas Tributadas no Mercado Interno e de Exportação"
+            }
+            CstPisCofins::CreditoReceitasNaoTributadaEExportacao => {
+                "Operação com Direito a Crédito - Vinculada a Receitas Não-Tributadas no Mercado Interno e de Exportação"
+            }
+            CstPisCofins::CreditoReceitasTributadaNaoTributadaEExportacao => {
+                "Operação com Direito a Crédito - Vinculada a Receitas Tributadas e Não-Tributadas no Mercado Interno, e de Exportação"
+            }
+            CstPisCofins::PresumidoReceitaTributada => {
+                "Crédito Presumido - Operação de Aquisição Vinculada Exclusivamente a Receita Tributada no Mercado Interno"
+            }
+            CstPisCofins::PresumidoReceitaNaoTributada => {
+                "Crédito Presumido - Operação de Aquisição Vinculada Exclusivamente a Receita Não-Tributada no Mercado Interno"
+            }
+            CstPisCofins::PresumidoReceitaExportacao => {
+                "Crédito Presumido - Operação de Aquisição Vinculada Exclusivamente a Receita de Exportação"
+            }
+            CstPisCofins::PresumidoReceitasTributadaENaoTributada => {
+                "Crédito Presumido - Operação de Aquisição Vinculada a Receitas Tributadas e Não-Tributadas no Mercado Interno"
+            }
+            CstPisCofins::PresumidoReceitasTributadaEExportacao => {
+                "Crédito Presumido - Operação de Aquisição Vinculada a Receitas Tributadas no Mercado Interno e de Exportação"
+            }
+            CstPisCofins::PresumidoReceitasNaoTributadaEExportacao => {
+                "Crédito Presumido - Operação de Aquisição Vinculada a Receitas Não-Tributadas no Mercado Interno e de Exportação"
+            }
+            CstPisCofins::PresumidoReceitasTributadaNaoTributadaEExportacao => {
+                "Crédito Presumido - Operação de Aquisição Vinculada a Receitas Tributadas e Não-Tributadas no Mercado Interno, e de Exportação"
+            }
+            CstPisCofins::PresumidoOutrasOperacoes => "Crédito Presumido - Outras Operações",
+            CstPisCofins::AquisicaoSemDireitoACredito => {
+                "Operação de Aquisição sem Direito a Crédito"
+            }
+            CstPisCofins::AquisicaoComIsencao => "Operação de Aquisição com Isenção",
+            CstPisCofins::AquisicaoComSuspensao => "Operação de Aquisição com Suspensão",
+            CstPisCofins::AquisicaoAliquotaZero => "Operação de Aquisição a Alíquota Zero",
+            CstPisCofins::AquisicaoSemIncidencia => {
+                "Operação de Aquisição sem Incidência da Contribuição"
+            }
+            CstPisCofins::AquisicaoPorSubstituicaoTributaria => {
+                "Operação de Aquisição por Substituição Tributária"
+            }
+            CstPisCofins::OutrasOperacoesDeEntrada => "Outras Operações de Entrada",
+            CstPisCofins::OutrasOperacoes => "Outras Operações",
+        }
+    }
+}
+
+impl TryFrom<u16> for CstPisCofins {
+    type Error = CstError;
+
+    fn try_from(code: u16) -> Result<Self, Self::Error> {
+        CstPisCofins::from_code(code).ok_or(CstError::InvalidCode(code))
+    }
+}
+
+impl FromStr for CstPisCofins {
+    type Err = CstError;
+
+    /// Parse "1" or "01".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim();
+
+        if trimmed.is_empty() || trimmed.len() > 2 || !trimmed.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(CstError::InvalidFormat(s.to_string()));
+        }
+
+        let code: u16 = trimmed
+            .parse()
+            .map_err(|_| CstError::InvalidFormat(s.to_string()))?;
+
+        CstPisCofins::try_from(code)
+    }
+}
+
+impl fmt::Display for CstPisCofins {
+    /// Format with 2 digits: "01".
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}", self.code())
+    }
+}
+
+#[cfg(test)]
+mod cst_tests {
+    use super::*;
+    use crate::{
+        CSTS_NAO_TRIBUTADOS, CST_CREDITO, CST_CREDITO_BASICO, CST_CREDITO_PRESUMIDO,
+        CST_RECEITA_BRUTA,
+    };
+
+    // cargo test -- --show-output cst_tests
+
+    #[test]
+    fn all_variants_round_trip() {
+        for cst in CstPisCofins::iter() {
+            assert_eq!(CstPisCofins::try_from(cst.code()), Ok(cst));
+            assert_eq!(cst.to_string().parse::<CstPisCofins>(), Ok(cst));
+        }
+        assert!(CstPisCofins::ALL.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn invalid_cst() {
+        assert_eq!(CstPisCofins::try_from(0), Err(CstError::InvalidCode(0)));
+        assert_eq!(CstPisCofins::try_from(48), Err(CstError::InvalidCode(48)));
+        assert_eq!(
+            "101".parse::<CstPisCofins>(),
+            Err(CstError::InvalidFormat("101".to_string()))
+        );
+        assert_eq!(
+            "5a".parse::<CstPisCofins>(),
+            Err(CstError::InvalidFormat("5a".to_string()))
+        );
+    }
+
+    #[test]
+    fn same_codes_as_constants() {
+        // cargo test -- --show-output same_codes_as_constants
+        assert_eq!(
+            CST_CREDITO,
+            [50, 51, 52, 53, 54, 55, 56, 60, 61, 62, 63, 64, 65, 66]
+        );
+        assert_eq!(CST_CREDITO_BASICO, [50, 51, 52, 53, 54, 55, 56]);
+        assert_eq!(CST_CREDITO_PRESUMIDO, [60, 61, 62, 63, 64, 65, 66]);
+        assert_eq!(CST_RECEITA_BRUTA, [1, 2, 3, 4, 5, 6, 7, 8, 9, 49]);
+        assert_eq!(CSTS_NAO_TRIBUTADOS, [4, 6, 7, 8, 9, 49]);
+    }
+}
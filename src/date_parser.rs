@@ -0,0 +1,321 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use std::fmt;
+
+/// Date formats recognized by [`DateParser`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DateFormat {
+    /// "06122022"
+    Ddmmyyyy,
+    /// "061222"
+    Ddmmyy,
+    /// "06/12/2022" or "6/12/2022"
+    DdMmYyyySlash,
+    /// "06/12/22"
+    DdMmYySlash,
+    /// "2022-12-06"
+    IsoDate,
+    /// "2022-12-06T00:00:00-03:00", "2022-12-06T10:30:00Z" or "2022-12-06T10:30:00"
+    IsoDateTime,
+}
+
+impl fmt::Display for DateFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let pattern = match self {
+            DateFormat::Ddmmyyyy => "DDMMYYYY",
+            DateFormat::Ddmmyy => "DDMMYY",
+            DateFormat::DdMmYyyySlash => "DD/MM/YYYY",
+            DateFormat::DdMmYySlash => "DD/MM/YY",
+            DateFormat::IsoDate => "YYYY-MM-DD",
+            DateFormat::IsoDateTime => "ISO 8601 date-time",
+        };
+        write!(f, "{pattern}")
+    }
+}
+
+/// Errors returned by [`DateParser::parse`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DateError {
+    /// Empty or only whitespace.
+    Empty,
+    /// The text does not look like any known format.
+    UnknownFormat(String),
+    /// The text has the shape of `format`, but is not a valid date.
+    InvalidDate { format: DateFormat, text: String },
+}
+
+impl fmt::Display for DateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DateError::Empty => write!(f, "empty date"),
+            DateError::UnknownFormat(text) => write!(f, "unknown date format: '{text}'"),
+            DateError::InvalidDate { format, text } => {
+                write!(f, "invalid date in format {format}: '{text}'")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DateError {}
+
+/**
+Parse dates in the formats of [`DateFormat`].
+
+Two-digit years below the pivot (70 by default) are in the 2000s,
+the others in the 1900s.
+
+Never prints and never panics, so it can be used inside parallel iterators.
+
+Example:
+```
+    use claudiofsr_lib::{DateError, DateFormat, DateParser};
+    use chrono::NaiveDate;
+
+    let parser = DateParser::new();
+    let date = NaiveDate::from_ymd_opt(2022, 12, 6);
+
+    assert_eq!(parser.parse("06122022").ok(), date);
+    assert_eq!(parser.parse("06/12/2022").ok(), date);
+    assert_eq!(parser.parse("2022-12-06").ok(), date);
+    assert_eq!(parser.parse("2022-12-06T00:00:00-03:00").ok(), date);
+    assert_eq!(parser.parse("06/12/22").ok(), date);
+
+    let parser = DateParser::new().with_pivot(30);
+    assert_eq!(parser.parse("010145").ok(), NaiveDate::from_ymd_opt(1945, 1, 1));
+
+    assert_eq!(
+        parser.parse("29021973"),
+        Err(DateError::InvalidDate {
+            format: DateFormat::Ddmmyyyy,
+            text: "29021973".to_string(),
+        })
+    );
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DateParser {
+    pivot: u32,
+}
+
+impl Default for DateParser {
+    fn default() -> Self {
+        DateParser::new()
+    }
+}
+
+impl DateParser {
+    /// Parser with pivot 70: "69" is 2069 and "70" is 1970.
+    pub const fn new() -> Self {
+        DateParser { pivot: 70 }
+    }
+
+    /// Two-digit years below `pivot` are in the 2000s.
+    pub const fn with_pivot(mut self, pivot: u32) -> Self {
+        self.pivot = pivot;
+        self
+    }
+
+    /// Two-digit years below the pivot are in the 2000s, the others in the 1900s.
+    pub const fn pivot(&self) -> u32 {
+        self.pivot
+    }
+
+    /// Detect the format of the text, without checking if the date exists.
+    pub fn detect(&self, text: &str) -> Option<DateFormat> {
+        let text = text.trim();
+        let bytes = text.as_bytes();
+        let all_digits = |bytes: &[u8]| !bytes.is_empty() && bytes.iter().all(u8::is_ascii_digit);
+
+        if all_digits(bytes) {
+            return match bytes.len() {
+                8 => Some(DateFormat::Ddmmyyyy),
+                6 => Some(DateFormat::Ddmmyy),
+                _ => None,
+            };
+        }
+
+        let is_iso_date = |bytes: &[u8]| {
+            bytes.len() == 10
+                && bytes[4] == b'-'
+                && bytes[7] == b'-'
+                && all_digits(&bytes[..4])
+                && all_digits(&bytes[5..7])
+                && all_digits(&bytes[8..])
+        };
+
+        if is_iso_date(bytes) {
+            return Some(DateFormat::IsoDate);
+        }
+
+        if bytes.len() > 10 && matches!(bytes[10], b'T' | b't' | b' ') && is_iso_date(&bytes[..10])
+        {
+            return Some(DateFormat::IsoDateTime);
+        }
+
+        let parts: Vec<&str> = text.split('/').collect();
+        match parts.as_slice() {
+            [day, month, year]
+                if (1..=2).contains(&day.len())
+                    && (1..=2).contains(&month.len())
+                    && parts.iter().all(|part| all_digits(part.as_bytes())) =>
+            {
+                match year.len() {
+                    4 => Some(DateFormat::DdMmYyyySlash),
+                    2 => Some(DateFormat::DdMmYySlash),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /**
+    Parse a date in one of the formats of [`DateFormat`], ignoring surrounding whitespace:
+    "DDMMYYYY", "DDMMYY", "DD/MM/YYYY" (day and month may have one digit),
+    "DD/MM/YY", "YYYY-MM-DD" and ISO 8601 date-time, whose time and offset are discarded.
+
+    The two-digit years of "DDMMYY" and "DD/MM/YY" use the [pivot](DateParser::pivot).
+
+    The format is detected first ([`DateError::UnknownFormat`]), then the date
+    is checked ([`DateError::InvalidDate`] for "31/02/2022").
+    */
+    pub fn parse(&self, text: &str) -> Result<NaiveDate, DateError> {
+        let text = text.trim();
+
+        if text.is_empty() {
+            return Err(DateError::Empty);
+        }
+
+        let format = self
+            .detect(text)
+            .ok_or_else(|| DateError::UnknownFormat(text.to_string()))?;
+
+        let date: Option<NaiveDate> = match format {
+            DateFormat::Ddmmyyyy => ymd(&text[4..], &text[2..4], &text[..2]),
+            DateFormat::Ddmmyy => self
+                .two_digit_year(&text[4..])
+                .and_then(|year| ymd(&year.to_string(), &text[2..4], &text[..2])),
+            DateFormat::DdMmYyyySlash | DateFormat::DdMmYySlash => {
+                let parts: Vec<&str> = text.split('/').collect();
+                match format {
+                    DateFormat::DdMmYyyySlash => ymd(parts[2], parts[1], parts[0]),
+                    _ => self
+                        .two_digit_year(parts[2])
+                        .and_then(|year| ymd(&year.to_string(), parts[1], parts[0])),
+                }
+            }
+            DateFormat::IsoDate => ymd(&text[..4], &text[5..7], &text[8..]),
+            DateFormat::IsoDateTime => DateTime::parse_from_rfc3339(text)
+                .map(|datetime| datetime.date_naive())
+                .or_else(|_| {
+                    NaiveDateTime::parse_from_str(
+                        &text.replace(['t', ' '], "T"),
+                        "%Y-%m-%dT%H:%M:%S%.f",
+                    )
+                    .map(|datetime| datetime.date())
+                })
+                .ok(),
+        };
+
+        date.ok_or_else(|| DateError::InvalidDate {
+            format,
+            text: text.to_string(),
+        })
+    }
+
+    fn two_digit_year(&self, year: &str) -> Option<u32> {
+        let year: u32 = year.parse().ok()?;
+        match year < self.pivot {
+            true => Some(2000 + year),
+            false => Some(1900 + year),
+        }
+    }
+}
+
+/// Parse a date with the default [`DateParser`].
+pub fn parse_date(text: &str) -> Result<NaiveDate, DateError> {
+    DateParser::new().parse(text)
+}
+
+fn ymd(year: &str, month: &str, day: &str) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(year.parse().ok()?, month.parse().ok()?, day.parse().ok()?)
+}
+
+#[cfg(test)]
+mod date_parser_tests {
+    use super::*;
+
+    // cargo test -- --show-output date_parser_tests
+
+    #[test]
+    fn formats() {
+        let parser = DateParser::new();
+        let date = NaiveDate::from_ymd_opt(2024, 2, 29);
+
+        let valid = [
+            ("29022024", DateFormat::Ddmmyyyy),
+            ("290224", DateFormat::Ddmmyy),
+            ("29/2/2024", DateFormat::DdMmYyyySlash),
+            (" 29/02/24 ", DateFormat::DdMmYySlash),
+            ("2024-02-29", DateFormat::IsoDate),
+            ("2024-02-29T23:59:59Z", DateFormat::IsoDateTime),
+            ("2024-02-29T00:00:00-03:00", DateFormat::IsoDateTime),
+            ("2024-02-29 12:00:00.123", DateFormat::IsoDateTime),
+        ];
+
+        for (text, format) in valid {
+            assert_eq!(parser.detect(text), Some(format), "{text}");
+            assert_eq!(parser.parse(text).ok(), date, "{text}");
+        }
+    }
+
+    #[test]
+    fn pivot() {
+        let parser = DateParser::default();
+        assert_eq!(
+            parser.parse("01/01/69").ok(),
+            NaiveDate::from_ymd_opt(2069, 1, 1)
+        );
+        assert_eq!(
+            parser.parse("01/01/70").ok(),
+            NaiveDate::from_ymd_opt(1970, 1, 1)
+        );
+
+        let parser = DateParser::new().with_pivot(100);
+        assert_eq!(parser.pivot(), 100);
+        assert_eq!(
+            parser.parse("010199").ok(),
+            NaiveDate::from_ymd_opt(2099, 1, 1)
+        );
+
+        let parser = DateParser::new().with_pivot(0);
+        assert_eq!(
+            parser.parse("010100").ok(),
+            NaiveDate::from_ymd_opt(1900, 1, 1)
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let parser = DateParser::new();
+
+        assert_eq!(parser.parse("  "), Err(DateError::Empty));
+        assert_eq!(
+            parser.parse("2024/02/29"),
+            Err(DateError::UnknownFormat("2024/02/29".to_string()))
+        );
+        assert_eq!(parser.detect("1234567"), None);
+        assert_eq!(parser.detect("06-12-2022"), None);
+
+        let error = parser.parse("2023-02-29T00:00:00-03:00").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid date in format ISO 8601 date-time: '2023-02-29T00:00:00-03:00'"
+        );
+
+        let error = parser.parse("31/04/2024").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid date in format DD/MM/YYYY: '31/04/2024'"
+        );
+    }
+}
@@ -0,0 +1,627 @@
+use crate::{
+    number_parser::parse_number, NumberError, NumberFormat, NumberLocale, RoundWith, RoundingMode,
+};
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    iter::Sum,
+    ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+/// Minimum number of decimal places of a division result.
+const DIVISION_SCALE: u32 = 20;
+
+/**
+Exact decimal number: `mantissa * 10^(-scale)`.
+
+Sums and products of amounts are exact, unlike `f64`.
+Division keeps at least 20 decimal places, rounded half away from zero.
+Products that do not fit are rounded the same way, to 20 places or fewer.
+
+Arithmetic operators panic on overflow, like integers in debug mode;
+use the `checked_*` methods to get `None` instead.
+
+Equality, ordering and hashing consider the value: `1.5 == 1.50`.
+
+Example:
+```
+    use claudiofsr_lib::Decimal;
+
+    let price: Decimal = "1.234,56".parse().unwrap();
+    let quantity = Decimal::from(3);
+    let rate: Decimal = "0.0165".parse().unwrap(); // PIS
+
+    let total = price * quantity;
+    assert_eq!(total.to_string(), "3703.68");
+    assert_eq!((total * rate).round(2).to_string(), "61.11");
+    assert_eq!(total.thousands_separator(2), "3.703,68");
+
+    let items = vec![Decimal::new(10, 2); 100_000]; // 0,10 each
+    let sum: Decimal = items.iter().sum();
+    assert_eq!(sum, Decimal::from(10_000));
+
+    let f: f64 = (0..100_000).map(|_| 0.10).sum();
+    assert_ne!(f, 10_000.0);
+
+    assert_eq!(Decimal::from_f64(0.1), Some(Decimal::new(1, 1)));
+    assert_eq!((Decimal::from(1) / Decimal::from(8)).to_string(), "0.125");
+```
+*/
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Decimal {
+    mantissa: i128,
+    scale: u32,
+}
+
+impl Decimal {
+    pub const ZERO: Decimal = Decimal::new(0, 0);
+    pub const ONE: Decimal = Decimal::new(1, 0);
+
+    /// Build from the mantissa and the number of decimal places: `new(12345, 2)` is 123.45.
+    pub const fn new(mantissa: i128, scale: u32) -> Self {
+        Decimal { mantissa, scale }
+    }
+
+    /// Parse detecting the decimal separator.
+    ///
+    /// See [`BrNumber`](crate::BrNumber) for the accepted formats.
+    pub fn parse(text: &str) -> Result<Self, NumberError> {
+        parse_number(text, NumberLocale::Auto)
+    }
+
+    /// Parse with the decimal separator of the locale.
+    pub fn parse_with(text: &str, locale: NumberLocale) -> Result<Self, NumberError> {
+        parse_number(text, locale)
+    }
+
+    /// The digits as an integer, with the sign.
+    pub const fn mantissa(&self) -> i128 {
+        self.mantissa
+    }
+
+    /// Number of decimal places.
+    pub const fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    pub const fn is_zero(&self) -> bool {
+        self.mantissa == 0
+    }
+
+    pub const fn is_negative(&self) -> bool {
+        self.mantissa < 0
+    }
+
+    /// Check if there are no fractional digits other than zero.
+    pub fn is_integer(&self) -> bool {
+        self.integer_part().is_some()
+    }
+
+    /// The value without the decimal places, if they are all zero.
+    pub(crate) fn integer_part(&self) -> Option<i128> {
+        let divisor = 10_i128.checked_pow(self.scale)?;
+        (self.mantissa % divisor == 0).then_some(self.mantissa / divisor)
+    }
+
+    /// Integer and fractional digits of the absolute value.
+    fn split_abs(&self) -> (u128, u128) {
+        let mantissa = self.mantissa.unsigned_abs();
+        match 10_u128.checked_pow(self.scale) {
+            Some(divisor) => (mantissa / divisor, mantissa % divisor),
+            None => (0, mantissa),
+        }
+    }
+
+    pub const fn abs(&self) -> Self {
+        Decimal::new(self.mantissa.abs(), self.scale)
+    }
+
+    /// Remove trailing zeros of the decimal places: 1.500 -> 1.5.
+    pub fn normalize(&self) -> Self {
+        let mut decimal = *self;
+        while decimal.scale > 0 && decimal.mantissa % 10 == 0 {
+            decimal.mantissa /= 10;
+            decimal.scale -= 1;
+        }
+        decimal
+    }
+
+    /// Round to `places` decimal places, ties away from zero.
+    ///
+    /// The result has exactly `places` decimal places, when it fits.
+    pub fn round(&self, places: u32) -> Self {
+        self.round_with(places, RoundingMode::HalfUp)
+    }
+
+    /// Same value with more decimal places, if it does not overflow.
+    fn rescale(&self, scale: u32) -> Option<Self> {
+        let factor = 10_i128.checked_pow(scale.checked_sub(self.scale)?)?;
+        let mantissa = self.mantissa.checked_mul(factor)?;
+        Some(Decimal::new(mantissa, scale))
+    }
+
+    /// Both mantissas with the same scale.
+    fn align(&self, other: &Self) -> Option<(i128, i128, u32)> {
+        let scale = self.scale.max(other.scale);
+        let a = self.rescale(scale)?;
+        let b = other.rescale(scale)?;
+        Some((a.mantissa, b.mantissa, scale))
+    }
+
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        let (a, b, scale) = self.align(&other)?;
+        Some(Decimal::new(a.checked_add(b)?, scale))
+    }
+
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.checked_add(other.checked_neg()?)
+    }
+
+    pub fn checked_neg(self) -> Option<Self> {
+        Some(Decimal::new(self.mantissa.checked_neg()?, self.scale))
+    }
+
+    /// Exact product or, if it does not fit, rounded half away from zero
+    /// to at most 20 decimal places, like [`Decimal::checked_div`].
+    ///
+    /// Returns `None` if the integer part overflows.
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        let product = |a: Decimal, b: Decimal| {
+            let mantissa = a.mantissa.checked_mul(b.mantissa)?;
+            Some(Decimal::new(mantissa, a.scale.checked_add(b.scale)?))
+        };
+
+        product(self, other)
+            .or_else(|| product(self.normalize(), other.normalize()))
+            .or_else(|| self.normalize().rounded_mul(other.normalize()))
+    }
+
+    /// Product computed with 256 bits, dropping decimal places until it fits in `i128`.
+    fn rounded_mul(self, other: Self) -> Option<Self> {
+        let negative = self.is_negative() != other.is_negative();
+        let mut limbs = wide_mul(self.mantissa.unsigned_abs(), other.mantissa.unsigned_abs());
+        let mut scale = u64::from(self.scale) + u64::from(other.scale);
+        let mut dropped = 0;
+
+        let low = |limbs: &[u64; 4]| u128::from(limbs[0]) | u128::from(limbs[1]) << 64;
+
+        while limbs[2] != 0
+            || limbs[3] != 0
+            || low(&limbs) > i128::MAX as u128
+            || scale > u64::from(DIVISION_SCALE)
+        {
+            if scale == 0 {
+                return None;
+            }
+            dropped = div_rem_10(&mut limbs);
+            scale -= 1;
+        }
+
+        let mut mantissa = low(&limbs) + u128::from(dropped >= 5);
+        if mantissa > i128::MAX as u128 {
+            // Rounding up to 2^127: one more place.
+            scale = scale.checked_sub(1)?;
+            mantissa = (mantissa + 5) / 10;
+        }
+
+        let mantissa = mantissa as i128;
+        let decimal = Decimal::new(if negative { -mantissa } else { mantissa }, scale as u32);
+        Some(decimal.normalize())
+    }
+
+    /// Division with at least 20 decimal places, rounded half away from zero.
+    ///
+    /// Returns `None` on division by zero or overflow.
+    pub fn checked_div(self, other: Self) -> Option<Self> {
+        if other.is_zero() {
+            return None;
+        }
+
+        let (numerator, denominator, _) = self.align(&other)?;
+        let negative = (numerator < 0) != (denominator < 0);
+        let numerator = numerator.checked_abs()?;
+        let denominator = denominator.checked_abs()?;
+
+        let min_scale = self.scale.max(other.scale);
+        let scale = min_scale.max(DIVISION_SCALE);
+
+        // Long division, one decimal place at a time.
+        let mut quotient = numerator / denominator;
+        let mut remainder = numerator % denominator;
+
+        for _ in 0..scale {
+            remainder = remainder.checked_mul(10)?;
+            let digit = remainder / denominator;
+            remainder %= denominator;
+            quotient = quotient.checked_mul(10)?.checked_add(digit)?;
+        }
+
+        if remainder.checked_mul(2)? >= denominator {
+            quotient = quotient.checked_add(1)?;
+        }
+
+        let mut result = Decimal::new(if negative { -quotient } else { quotient }, scale);
+        while result.scale > min_scale && result.mantissa % 10 == 0 {
+            result.mantissa /= 10;
+            result.scale -= 1;
+        }
+
+        Some(result)
+    }
+
+    /// Convert from the shortest decimal representation of the `f64`: 0.1 -> 0.1.
+    ///
+    /// Returns `None` for NaN, infinity, or values that do not fit.
+    pub fn from_f64(value: f64) -> Option<Self> {
+        if !value.is_finite() {
+            return None;
+        }
+
+        // Display of f64 gives the shortest representation, without exponent.
+        Decimal::parse_with(&value.to_string(), NumberLocale::EnUs)
+            .ok()
+            .map(|number| number.normalize())
+    }
+
+    /// Convert from the shortest decimal representation of the `f32`.
+    pub fn from_f32(value: f32) -> Option<Self> {
+        if !value.is_finite() {
+            return None;
+        }
+
+        Decimal::parse_with(&value.to_string(), NumberLocale::EnUs)
+            .ok()
+            .map(|number| number.normalize())
+    }
+
+    /// The nearest `f64`.
+    pub fn to_f64(&self) -> f64 {
+        // Parsing the decimal text gives the correctly rounded value.
+        format!("{}e-{}", self.mantissa, self.scale)
+            .parse()
+            .unwrap_or(f64::NAN)
+    }
+
+    /// The nearest `f32`.
+    pub fn to_f32(&self) -> f32 {
+        format!("{}e-{}", self.mantissa, self.scale)
+            .parse()
+            .unwrap_or(f32::NAN)
+    }
+
+    /**
+    Format with thousands separator, rounded to `decimal` places.
+
+    Same format as [`crate::thousands_separator`]:
+    ```
+        use claudiofsr_lib::Decimal;
+
+        let value = Decimal::new(-2987954368369177, 6);
+        assert_eq!(value.thousands_separator(2), "-2.987.954.368,37");
+        assert_eq!(Decimal::new(5, 1).thousands_separator(0), "1");
+    ```
+    */
+    pub fn thousands_separator(&self, decimal: usize) -> String {
+        NumberFormat::pt_br().decimals(decimal).format(*self)
+    }
+}
+
+/// Full product of two `u128`: four 64-bit limbs, least significant first.
+fn wide_mul(a: u128, b: u128) -> [u64; 4] {
+    let a = [a as u64, (a >> 64) as u64];
+    let b = [b as u64, (b >> 64) as u64];
+    let mut limbs = [0_u64; 4];
+
+    for i in 0..2 {
+        let mut carry: u128 = 0;
+        for j in 0..2 {
+            // At most (2^64 - 1)^2 + 2 * (2^64 - 1) = 2^128 - 1.
+            let t = u128::from(a[i]) * u128::from(b[j]) + u128::from(limbs[i + j]) + carry;
+            limbs[i + j] = t as u64;
+            carry = t >> 64;
+        }
+        limbs[i + 2] = carry as u64;
+    }
+
+    limbs
+}
+
+/// Divide the limbs by 10 in place, returning the remainder.
+fn div_rem_10(limbs: &mut [u64; 4]) -> u64 {
+    let mut remainder: u128 = 0;
+
+    for limb in limbs.iter_mut().rev() {
+        let t = remainder << 64 | u128::from(*limb);
+        *limb = (t / 10) as u64;
+        remainder = t % 10;
+    }
+
+    remainder as u64
+}
+
+impl RoundWith for Decimal {
+    /// The result has exactly `places` decimal places, when it fits.
+    fn round_with(self, places: u32, mode: RoundingMode) -> Self {
+        if places >= self.scale {
+            return self.rescale(places).unwrap_or(self);
+        }
+
+        let mantissa = match 10_i128.checked_pow(self.scale - places) {
+            Some(divisor) => mode.divide(self.mantissa, divisor),
+            // |mantissa| < 10^39 / 2: less than half of the last place.
+            None => match mode {
+                RoundingMode::Floor if self.is_negative() => -1,
+                RoundingMode::Ceiling if self.mantissa > 0 => 1,
+                _ => 0,
+            },
+        };
+
+        Decimal::new(mantissa, places)
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Decimal {}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Decimal {
+    /// Sign, then integer part, then fractional part: nothing is rescaled.
+    fn cmp(&self, other: &Self) -> Ordering {
+        let ordering = self.mantissa.signum().cmp(&other.mantissa.signum());
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+
+        let (integer_a, fraction_a) = self.split_abs();
+        let (integer_b, fraction_b) = other.split_abs();
+
+        let ordering = integer_a
+            .cmp(&integer_b)
+            .then_with(|| cmp_fractions((fraction_a, self.scale), (fraction_b, other.scale)));
+
+        match self.is_negative() {
+            true => ordering.reverse(),
+            false => ordering,
+        }
+    }
+}
+
+/// Compare `a / 10^scale_a` with `b / 10^scale_b`, both less than 1.
+fn cmp_fractions((a, scale_a): (u128, u32), (b, scale_b): (u128, u32)) -> Ordering {
+    if scale_a > scale_b {
+        return cmp_fractions((b, scale_b), (a, scale_a)).reverse();
+    }
+
+    // b = quotient at scale_a, plus the remainder in the extra places.
+    let (quotient, remainder) = match 10_u128.checked_pow(scale_b - scale_a) {
+        Some(divisor) => (b / divisor, b % divisor),
+        None => (0, b),
+    };
+
+    a.cmp(&quotient).then(match remainder {
+        0 => Ordering::Equal,
+        _ => Ordering::Less,
+    })
+}
+
+impl Hash for Decimal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let normalized = self.normalize();
+        normalized.mantissa.hash(state);
+        normalized.scale.hash(state);
+    }
+}
+
+impl Add for Decimal {
+    type Output = Decimal;
+
+    fn add(self, other: Self) -> Self::Output {
+        self.checked_add(other)
+            .expect("Decimal overflow in addition")
+    }
+}
+
+impl Sub for Decimal {
+    type Output = Decimal;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self.checked_sub(other)
+            .expect("Decimal overflow in subtraction")
+    }
+}
+
+impl Mul for Decimal {
+    type Output = Decimal;
+
+    fn mul(self, other: Self) -> Self::Output {
+        self.checked_mul(other)
+            .expect("Decimal overflow in multiplication")
+    }
+}
+
+impl Div for Decimal {
+    type Output = Decimal;
+
+    fn div(self, other: Self) -> Self::Output {
+        self.checked_div(other)
+            .expect("Decimal division by zero or overflow")
+    }
+}
+
+impl Neg for Decimal {
+    type Output = Decimal;
+
+    fn neg(self) -> Self::Output {
+        self.checked_neg().expect("Decimal overflow in negation")
+    }
+}
+
+impl AddAssign for Decimal {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Decimal {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl Sum for Decimal {
+    fn sum<I: Iterator<Item = Decimal>>(iter: I) -> Self {
+        iter.fold(Decimal::ZERO, Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Decimal> for Decimal {
+    fn sum<I: Iterator<Item = &'a Decimal>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+macro_rules! impl_from_integer {
+    ( $($t:ty),* ) => {
+        $(
+            impl From<$t> for Decimal {
+                fn from(value: $t) -> Self {
+                    Decimal::new(i128::from(value), 0)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(i8, i16, i32, i64, u8, u16, u32, u64);
+
+impl From<Decimal> for f64 {
+    fn from(decimal: Decimal) -> Self {
+        decimal.to_f64()
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = NumberError;
+
+    /// Parse "1.234,56", "1234.56" or any format accepted by [`BrNumber`](crate::BrNumber).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Decimal::parse(s)
+    }
+}
+
+impl fmt::Display for Decimal {
+    /// Format with dot decimals and all decimal places: "-1234.50".
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = self.mantissa.unsigned_abs().to_string();
+        let scale = self.scale as usize;
+        let sign = if self.is_negative() { "-" } else { "" };
+
+        if scale == 0 {
+            return write!(f, "{sign}{digits}");
+        }
+
+        let digits = format!("{digits:0>width$}", width = scale + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - scale);
+        write!(f, "{sign}{integer}.{fraction}")
+    }
+}
+
+#[cfg(test)]
+mod decimal_tests {
+    use super::*;
+
+    // cargo test -- --show-output decimal_tests
+
+    fn dec(text: &str) -> Decimal {
+        Decimal::parse_with(text, NumberLocale::PtBr).unwrap()
+    }
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(dec("0,1") + dec("0,2"), dec("0,3"));
+        assert_eq!((dec("10,00") - dec("10,5")).to_string(), "-0.50");
+        assert_eq!((dec("1,5") * dec("-2,25")).to_string(), "-3.375");
+        assert_eq!((dec("10,00") / dec("4")).to_string(), "2.50");
+        assert_eq!((dec("2") / dec("3")).to_string(), "0.66666666666666666667");
+        assert_eq!(dec("1") / dec("-4") * dec("4"), dec("-1"));
+        assert_eq!(dec("1").checked_div(Decimal::ZERO), None);
+        assert_eq!(Decimal::new(i128::MAX, 0).checked_add(Decimal::ONE), None);
+    }
+
+    #[test]
+    fn product_rounded_when_it_does_not_fit() {
+        let third = dec("1") / dec("3");
+        assert_eq!((third * third).to_string(), "0.11111111111111111111");
+        assert_eq!((-third * third).to_string(), "-0.11111111111111111111");
+
+        let two_thirds = dec("2") / dec("3");
+        assert_eq!(
+            (two_thirds * two_thirds).to_string(),
+            "0.44444444444444444445"
+        );
+        assert_eq!((dec("1") / dec("7") * dec("7")).round(2), dec("1"));
+
+        let base = dec("1.234.567,89") / dec("3");
+        assert_eq!((base * dec("0,0165")).round(2), dec("6.790,12"));
+
+        let max = Decimal::new(i128::MAX, 0);
+        assert_eq!(max.checked_mul(dec("2")), None);
+        assert_eq!(
+            max.checked_mul(third),
+            Some(Decimal::new(56713727820156410576661963960426471137, 0))
+        );
+    }
+
+    #[test]
+    fn rounding_and_format() {
+        assert_eq!(dec("1,005").round(2).to_string(), "1.01");
+        assert_eq!(dec("-1,005").round(2).to_string(), "-1.01");
+        assert_eq!(dec("1,004").round(2).to_string(), "1.00");
+        assert_eq!(dec("2,5").round(0).to_string(), "3");
+        assert_eq!(dec("2,5").round(3).to_string(), "2.500");
+        assert_eq!(dec("-0,001").thousands_separator(2), "0,00");
+        assert_eq!(dec("1234567,891").thousands_separator(2), "1.234.567,89");
+        assert_eq!(Decimal::new(-5, 3).to_string(), "-0.005");
+    }
+
+    #[test]
+    fn comparison_and_conversion() {
+        assert_eq!(dec("1,50"), dec("1,5"));
+        assert!(dec("-2") < dec("1,999"));
+        assert!(Decimal::new(i128::MAX, 0) > Decimal::new(1, 30));
+
+        let mut values = [dec("3"), dec("-1,5"), dec("0,25")];
+        values.sort();
+        assert_eq!(values, [dec("-1,5"), dec("0,25"), dec("3")]);
+
+        let tiny = Decimal::new(5, 40);
+        assert!(Decimal::ZERO < tiny);
+        assert_ne!(Decimal::ZERO, tiny);
+        assert_eq!(tiny, Decimal::new(50, 41));
+        assert!(-tiny < Decimal::ZERO);
+        assert!(Decimal::new(4, 40) < tiny && tiny < Decimal::new(1, 39));
+        assert!(Decimal::new(i128::MAX, 40) < Decimal::new(2, 2));
+        assert!(Decimal::new(i128::MIN, 0) < Decimal::new(-1, 50));
+
+        let set: std::collections::HashSet<Decimal> = [Decimal::ZERO, tiny, dec("0,0")].into();
+        assert_eq!(set.len(), 2);
+        let set: std::collections::BTreeSet<Decimal> = [Decimal::ZERO, tiny, dec("0,0")].into();
+        assert_eq!(set.len(), 2);
+
+        assert_eq!(Decimal::from_f64(-1234.5678), Some(dec("-1.234,5678")));
+        assert_eq!(Decimal::from_f64(f64::NAN), None);
+        assert_eq!(f64::from(dec("1.234,56")), 1234.56);
+    }
+}
@@ -0,0 +1,424 @@
+use crate::Decimal;
+
+const UNIDADES: [&str; 20] = [
+    "zero",
+    "um",
+    "dois",
+    "três",
+    "quatro",
+    "cinco",
+    "seis",
+    "sete",
+    "oito",
+    "nove",
+    "dez",
+    "onze",
+    "doze",
+    "treze",
+    "quatorze",
+    "quinze",
+    "dezesseis",
+    "dezessete",
+    "dezoito",
+    "dezenove",
+];
+
+const DEZENAS: [&str; 10] = [
+    "",
+    "dez",
+    "vinte",
+    "trinta",
+    "quarenta",
+    "cinquenta",
+    "sessenta",
+    "setenta",
+    "oitenta",
+    "noventa",
+];
+
+const CENTENAS: [&str; 10] = [
+    "",
+    "cento",
+    "duzentos",
+    "trezentos",
+    "quatrocentos",
+    "quinhentos",
+    "seiscentos",
+    "setecentos",
+    "oitocentos",
+    "novecentos",
+];
+
+/// Singular and plural of the powers of one thousand, from 10^6 to 10^36.
+const ESCALAS: [(&str, &str); 11] = [
+    ("milhão", "milhões"),
+    ("bilhão", "bilhões"),
+    ("trilhão", "trilhões"),
+    ("quatrilhão", "quatrilhões"),
+    ("quintilhão", "quintilhões"),
+    ("sextilhão", "sextilhões"),
+    ("septilhão", "septilhões"),
+    ("octilhão", "octilhões"),
+    ("nonilhão", "nonilhões"),
+    ("decilhão", "decilhões"),
+    ("undecilhão", "undecilhões"),
+];
+
+/// Grammatical gender of the counted noun.
+///
+/// Affects "um/uma", "dois/duas" and the hundreds ("duzentos/duzentas").
+///
+/// Millions and above are masculine nouns: "dois milhões e duas mil".
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Genero {
+    #[default]
+    Masculino,
+    Feminino,
+}
+
+/**
+Cardinal numbers in words, in Brazilian Portuguese.
+
+Example:
+```
+    use claudiofsr_lib::{Genero, PorExtenso};
+
+    assert_eq!(1234.por_extenso(Genero::Masculino), "mil duzentos e trinta e quatro");
+    assert_eq!(2_001_000_u64.por_extenso(Genero::Masculino), "dois milhões e mil");
+    assert_eq!(201.por_extenso(Genero::Feminino), "duzentas e uma");
+    assert_eq!((-100_i8).por_extenso(Genero::Masculino), "menos cem");
+```
+*/
+pub trait PorExtenso {
+    fn por_extenso(&self, genero: Genero) -> String;
+}
+
+/**
+Monetary amounts in words, in reais, rounded to cents.
+
+Returns `None` for NaN and infinity.
+
+Example:
+```
+    use claudiofsr_lib::ValorPorExtenso;
+
+    assert_eq!(
+        1234.56.valor_por_extenso().unwrap(),
+        "mil duzentos e trinta e quatro reais e cinquenta e seis centavos"
+    );
+    assert_eq!(
+        2_000_000.valor_por_extenso().unwrap(),
+        "dois milhões de reais"
+    );
+    assert_eq!(
+        (-1.01).valor_por_extenso().unwrap(),
+        "menos um real e um centavo"
+    );
+    assert_eq!(f64::NAN.valor_por_extenso(), None);
+```
+*/
+pub trait ValorPorExtenso {
+    fn valor_por_extenso(&self) -> Option<String>;
+}
+
+macro_rules! impl_por_extenso_signed {
+    ( $($t:ty),* ) => {
+        $(
+            impl PorExtenso for $t {
+                fn por_extenso(&self, genero: Genero) -> String {
+                    com_sinal(*self < 0, cardinal(self.unsigned_abs().into(), genero))
+                }
+            }
+
+            impl ValorPorExtenso for $t {
+                fn valor_por_extenso(&self) -> Option<String> {
+                    Some(reais(*self < 0, self.unsigned_abs().into(), 0))
+                }
+            }
+        )*
+    };
+}
+
+impl_por_extenso_signed!(i8, i16, i32, i64, i128);
+
+macro_rules! impl_por_extenso_unsigned {
+    ( $($t:ty),* ) => {
+        $(
+            impl PorExtenso for $t {
+                fn por_extenso(&self, genero: Genero) -> String {
+                    cardinal((*self).into(), genero)
+                }
+            }
+
+            impl ValorPorExtenso for $t {
+                fn valor_por_extenso(&self) -> Option<String> {
+                    Some(reais(false, (*self).into(), 0))
+                }
+            }
+        )*
+    };
+}
+
+impl_por_extenso_unsigned!(u8, u16, u32, u64, u128);
+
+impl PorExtenso for usize {
+    fn por_extenso(&self, genero: Genero) -> String {
+        cardinal(*self as u128, genero)
+    }
+}
+
+impl PorExtenso for isize {
+    fn por_extenso(&self, genero: Genero) -> String {
+        com_sinal(*self < 0, cardinal(self.unsigned_abs() as u128, genero))
+    }
+}
+
+impl ValorPorExtenso for usize {
+    fn valor_por_extenso(&self) -> Option<String> {
+        Some(reais(false, *self as u128, 0))
+    }
+}
+
+impl ValorPorExtenso for isize {
+    fn valor_por_extenso(&self) -> Option<String> {
+        Some(reais(*self < 0, self.unsigned_abs() as u128, 0))
+    }
+}
+
+impl ValorPorExtenso for Decimal {
+    fn valor_por_extenso(&self) -> Option<String> {
+        let rounded = self.round(2);
+
+        // The scale is smaller than 2 only if the mantissa does not fit.
+        let divisor = 10_u128.pow(rounded.scale());
+        let mantissa = rounded.mantissa().unsigned_abs();
+        let centavos = (mantissa % divisor) * 10_u128.pow(2 - rounded.scale());
+
+        Some(reais(
+            rounded.is_negative(),
+            mantissa / divisor,
+            centavos as u8,
+        ))
+    }
+}
+
+impl ValorPorExtenso for f64 {
+    fn valor_por_extenso(&self) -> Option<String> {
+        Decimal::from_f64(*self)?.valor_por_extenso()
+    }
+}
+
+impl ValorPorExtenso for f32 {
+    fn valor_por_extenso(&self) -> Option<String> {
+        Decimal::from_f32(*self)?.valor_por_extenso()
+    }
+}
+
+fn com_sinal(negative: bool, text: String) -> String {
+    match negative {
+        true => format!("menos {text}"),
+        false => text,
+    }
+}
+
+/// Number from 1 to 999 in words.
+fn centena(value: u16, genero: Genero) -> String {
+    if value == 100 {
+        return "cem".to_string();
+    }
+
+    let unidade = |digit: u16| match (digit, genero) {
+        (1, Genero::Feminino) => "uma",
+        (2, Genero::Feminino) => "duas",
+        _ => UNIDADES[digit as usize],
+    };
+
+    let mut words: Vec<String> = Vec::new();
+
+    match (value / 100, genero) {
+        (0, _) => (),
+        (1, _) | (_, Genero::Masculino) => words.push(CENTENAS[(value / 100) as usize].to_string()),
+        (hundreds, Genero::Feminino) => {
+            words.push(CENTENAS[hundreds as usize].replace("tos", "tas"))
+        }
+    }
+
+    match value % 100 {
+        0 => (),
+        rest @ 1..=19 => words.push(unidade(rest).to_string()),
+        rest => {
+            words.push(DEZENAS[(rest / 10) as usize].to_string());
+            if rest % 10 > 0 {
+                words.push(unidade(rest % 10).to_string());
+            }
+        }
+    }
+
+    words.join(" e ")
+}
+
+fn cardinal(value: u128, genero: Genero) -> String {
+    if value == 0 {
+        return UNIDADES[0].to_string();
+    }
+
+    // Groups of three digits, from the least significant.
+    let mut grupos: Vec<u16> = Vec::new();
+    let mut rest = value;
+    while rest > 0 {
+        grupos.push((rest % 1000) as u16);
+        rest /= 1000;
+    }
+
+    let mut parts: Vec<(u16, String)> = Vec::new();
+
+    for (index, &grupo) in grupos.iter().enumerate().rev() {
+        let text = match (index, grupo) {
+            (_, 0) => continue,
+            (0, _) => centena(grupo, genero),
+            (1, 1) => "mil".to_string(),
+            (1, _) => format!("{} mil", centena(grupo, genero)),
+            (_, 1) => format!("um {}", ESCALAS[index - 2].0),
+            (_, _) => format!(
+                "{} {}",
+                centena(grupo, Genero::Masculino),
+                ESCALAS[index - 2].1
+            ),
+        };
+        parts.push((grupo, text));
+    }
+
+    // "e" before the last group if it is less than 100 or a multiple of 100:
+    // "mil e cem", "mil e vinte", but "mil duzentos e trinta".
+    let mut words = String::new();
+    let last = parts.len() - 1;
+
+    for (index, (grupo, text)) in parts.iter().enumerate() {
+        if index > 0 {
+            match index == last && (*grupo < 100 || grupo % 100 == 0) {
+                true => words += " e ",
+                false => words += " ",
+            }
+        }
+        words += text;
+    }
+
+    words
+}
+
+/// Amount in reais: "um milhão de reais e cinquenta centavos".
+fn reais(negative: bool, reais: u128, centavos: u8) -> String {
+    let moeda = match reais {
+        1 => "real",
+        // Exact millions: "um milhão de reais".
+        _ if reais >= 1_000_000 && reais % 1_000_000 == 0 => "de reais",
+        _ => "reais",
+    };
+
+    let centavo = match centavos {
+        1 => "centavo",
+        _ => "centavos",
+    };
+
+    let text = match (reais, centavos) {
+        (0, 0) => return "zero reais".to_string(),
+        (0, _) => format!("{} {centavo}", cardinal(centavos.into(), Genero::Masculino)),
+        (_, 0) => format!("{} {moeda}", cardinal(reais, Genero::Masculino)),
+        (_, _) => format!(
+            "{} {moeda} e {} {centavo}",
+            cardinal(reais, Genero::Masculino),
+            cardinal(centavos.into(), Genero::Masculino)
+        ),
+    };
+
+    com_sinal(negative, text)
+}
+
+#[cfg(test)]
+mod extenso_tests {
+    use super::*;
+
+    // cargo test -- --show-output extenso_tests
+
+    #[test]
+    fn cardinais() {
+        let valid = [
+            (0, "zero"),
+            (15, "quinze"),
+            (21, "vinte e um"),
+            (100, "cem"),
+            (101, "cento e um"),
+            (999, "novecentos e noventa e nove"),
+            (1000, "mil"),
+            (1001, "mil e um"),
+            (1100, "mil e cem"),
+            (1234, "mil duzentos e trinta e quatro"),
+            (21_000, "vinte e um mil"),
+            (1_000_000, "um milhão"),
+            (1_100_000, "um milhão e cem mil"),
+            (
+                1_234_567,
+                "um milhão duzentos e trinta e quatro mil quinhentos e sessenta e sete",
+            ),
+            (2_000_000_001, "dois bilhões e um"),
+        ];
+
+        for (value, text) in valid {
+            assert_eq!(value.por_extenso(Genero::Masculino), text);
+        }
+
+        assert!(u128::MAX
+            .por_extenso(Genero::Masculino)
+            .starts_with("trezentos e quarenta undecilhões"));
+        assert!(i128::MIN
+            .por_extenso(Genero::Masculino)
+            .starts_with("menos cento e setenta undecilhões"));
+    }
+
+    #[test]
+    fn feminino() {
+        let valid = [
+            (1, "uma"),
+            (2, "duas"),
+            (22, "vinte e duas"),
+            (200, "duzentas"),
+            (1_002, "mil e duas"),
+            (2_000, "duas mil"),
+            (522_201, "quinhentas e vinte e duas mil duzentas e uma"),
+            (2_002_000, "dois milhões e duas mil"),
+            (2_200_000, "dois milhões e duzentas mil"),
+        ];
+
+        for (value, text) in valid {
+            assert_eq!(value.por_extenso(Genero::Feminino), text);
+        }
+    }
+
+    #[test]
+    fn valores() {
+        let valid = [
+            (0.0, "zero reais"),
+            (0.01, "um centavo"),
+            (0.5, "cinquenta centavos"),
+            (1.0, "um real"),
+            (2.999, "três reais"),
+            (100.10, "cem reais e dez centavos"),
+            (1_000_000.0, "um milhão de reais"),
+            (1_000_000.5, "um milhão de reais e cinquenta centavos"),
+            (1_200_000.0, "um milhão e duzentos mil reais"),
+            (-3_000_000_000.0, "menos três bilhões de reais"),
+        ];
+
+        for (value, text) in valid {
+            assert_eq!(value.valor_por_extenso().as_deref(), Some(text));
+        }
+
+        let value = Decimal::new(-123456, 2);
+        assert_eq!(
+            value.valor_por_extenso().as_deref(),
+            Some("menos mil duzentos e trinta e quatro reais e cinquenta e seis centavos")
+        );
+        assert_eq!(1_u8.valor_por_extenso().as_deref(), Some("um real"));
+        assert_eq!(f32::INFINITY.valor_por_extenso(), None);
+    }
+}
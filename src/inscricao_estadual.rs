@@ -0,0 +1,559 @@
+use crate::{CheckDigit, DigitRule, Direction, StrExtension, Uf};
+use std::fmt;
+
+/// Value accepted for participants without a state registration.
+const ISENTO: &str = "ISENTO";
+
+/// Weights from 9 down to 2; shorter bases use the end of the slice.
+const PESOS_9_2: &[u32] = &[9, 8, 7, 6, 5, 4, 3, 2];
+
+/// Mod-11 with the weights applied from left to right: 11 - remainder, 10 and 11 give 0.
+const fn mod11(weights: &'static [u32]) -> CheckDigit {
+    CheckDigit::mod11(weights).with_direction(Direction::LeftToRight)
+}
+
+const PADRAO: CheckDigit = mod11(PESOS_9_2);
+
+/// AC and DF.
+const AC_DF_DV1: CheckDigit = mod11(&[4, 3, 2, 9, 8, 7, 6, 5, 4, 3, 2]);
+const AC_DF_DV2: CheckDigit = mod11(&[5, 4, 3, 2, 9, 8, 7, 6, 5, 4, 3, 2]);
+
+/// AL and RN: (sum * 10) mod 11, 10 gives 0.
+const fn mod11_vezes_10(weights: &'static [u32]) -> CheckDigit {
+    mod11(weights).with_rule(DigitRule::Custom(|remainder| remainder * 10 % 11 % 10))
+}
+
+/// AP: the first weight multiplies the constant `p` prepended to the digits.
+const AP_PESOS: &[u32] = &[1, 9, 8, 7, 6, 5, 4, 3, 2];
+
+/// AP range where 11 - remainder = 11 gives 1.
+const AP_FAIXA: DigitRule = DigitRule::Custom(|remainder| match remainder {
+    0 => 1,
+    1 => 0,
+    r => 11 - r,
+});
+
+/// GO range where remainder 1 gives 1.
+const GO_FAIXA: CheckDigit =
+    mod11(PESOS_9_2).with_rule(DigitRule::Custom(|remainder| match remainder {
+        0 | 1 => remainder,
+        r => 11 - r,
+    }));
+
+const MG_DV1: CheckDigit = CheckDigit::new(10, &[1, 2])
+    .with_direction(Direction::LeftToRight)
+    .with_sum_product_digits(true);
+const MG_DV2: CheckDigit = mod11(&[3, 2, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2]);
+
+const MT: CheckDigit = mod11(&[3, 2, 9, 8, 7, 6, 5, 4, 3, 2]);
+
+const PR_DV1: CheckDigit = mod11(&[3, 2, 7, 6, 5, 4, 3, 2]);
+const PR_DV2: CheckDigit = mod11(&[4, 3, 2, 7, 6, 5, 4, 3, 2]);
+
+/// PE (old CACEPE with 14 digits) and RO: 11 - remainder, 10 and 11 give 0 and 1.
+const fn mod11_menos_10(weights: &'static [u32]) -> CheckDigit {
+    mod11(weights).with_rule(DigitRule::Custom(|remainder| (11 - remainder) % 10))
+}
+
+const RJ: CheckDigit = mod11(&[2, 7, 6, 5, 4, 3, 2]);
+const RS: CheckDigit = mod11(&[2, 9, 8, 7, 6, 5, 4, 3, 2]);
+
+/// RR: sum mod 9.
+const RR: CheckDigit = CheckDigit::new(9, &[1, 2, 3, 4, 5, 6, 7, 8])
+    .with_direction(Direction::LeftToRight)
+    .with_rule(DigitRule::Remainder { overflow: 0 });
+
+/// SP: remainder mod 10.
+const fn mod11_sp(weights: &'static [u32]) -> CheckDigit {
+    CheckDigit::new(11, weights)
+        .with_direction(Direction::LeftToRight)
+        .with_rule(DigitRule::Custom(|remainder| remainder % 10))
+}
+
+const SP_DV1: CheckDigit = mod11_sp(&[1, 3, 4, 5, 6, 7, 8, 10]);
+const SP_DV2: CheckDigit = mod11_sp(&[3, 2, 10, 9, 8, 7, 6, 5, 4, 3, 2]);
+
+/// Errors returned when validating a state registration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InscricaoError {
+    /// Empty or only whitespace.
+    Empty,
+    /// A character other than a digit, a space or the mask (`.`, `-`, `/`) was found.
+    InvalidCharacter(char),
+    /// The number of digits is not accepted by the state.
+    InvalidLength { uf: Uf, length: usize },
+    /// The first digits are not accepted by the state.
+    InvalidPrefix { uf: Uf, inscricao: String },
+    /// The check digits do not match the calculated ones.
+    InvalidCheckDigit { uf: Uf, inscricao: String },
+}
+
+impl fmt::Display for InscricaoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InscricaoError::Empty => write!(f, "empty state registration"),
+            InscricaoError::InvalidCharacter(ch) => {
+                write!(f, "invalid character in state registration: {ch:?}")
+            }
+            InscricaoError::InvalidLength { uf, length } => {
+                write!(
+                    f,
+                    "invalid length of {uf} state registration: {length} digits"
+                )
+            }
+            InscricaoError::InvalidPrefix { uf, inscricao } => {
+                write!(f, "invalid prefix of {uf} state registration: {inscricao}")
+            }
+            InscricaoError::InvalidCheckDigit { uf, inscricao } => {
+                write!(
+                    f,
+                    "invalid check digit of {uf} state registration: {inscricao}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for InscricaoError {}
+
+/// Reason of a failed state-specific validation.
+enum Falha {
+    Length,
+    Prefix,
+    CheckDigit,
+}
+
+/**
+Inscrição Estadual (IE) validated by the rules of its state, or "ISENTO".
+
+The mask is removed before validation and the state's mask is applied by `Display`.
+In SP, the registration of rural producers starts with "P".
+
+Example:
+```
+    use claudiofsr_lib::{InscricaoError, InscricaoEstadual, Uf};
+
+    let ie = InscricaoEstadual::new(Uf::SP, "110.042.490.114").unwrap();
+    assert_eq!(ie.as_str(), "110042490114");
+    assert_eq!(ie.to_string(), "110.042.490.114");
+
+    let ie = InscricaoEstadual::new(Uf::MG, "0623079040081").unwrap();
+    assert_eq!(ie.to_string(), "062.307.904/0081");
+
+    let ie = InscricaoEstadual::new(Uf::RJ, " isento ").unwrap();
+    assert!(ie.is_isento());
+    assert_eq!(ie.to_string(), "ISENTO");
+
+    assert_eq!(
+        InscricaoEstadual::new(Uf::RS, "224/3658793"),
+        Err(InscricaoError::InvalidCheckDigit {
+            uf: Uf::RS,
+            inscricao: "2243658793".to_string()
+        })
+    );
+```
+
+<http://www.sintegra.gov.br/insc_est.html>
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InscricaoEstadual {
+    uf: Uf,
+    /// Digits, with the leading "P" of SP rural producers; `None` if exempt.
+    numero: Option<String>,
+}
+
+impl InscricaoEstadual {
+    /// Validate the registration of the state, with or without the mask, or "ISENTO".
+    ///
+    /// The rural producer of SP starts with "P". In MT, zeros on the left may be omitted.
+    pub fn new(uf: Uf, inscricao: &str) -> Result<Self, InscricaoError> {
+        let text = inscricao.trim();
+
+        if text.is_empty() {
+            return Err(InscricaoError::Empty);
+        }
+
+        if text.eq_ignore_ascii_case(ISENTO) {
+            return Ok(InscricaoEstadual { uf, numero: None });
+        }
+
+        let (rural, text) = match text.strip_prefix(['P', 'p']) {
+            Some(rest) if uf == Uf::SP => (true, rest),
+            _ => (false, text),
+        };
+
+        if let Some(ch) = text
+            .chars()
+            .find(|ch| !ch.is_ascii_digit() && !matches!(ch, '.' | '-' | '/' | ' '))
+        {
+            return Err(InscricaoError::InvalidCharacter(ch));
+        }
+
+        let mut digits = text.remove_non_digits();
+
+        // MT is completed with zeros on the left.
+        if uf == Uf::MT && (1..11).contains(&digits.len()) {
+            digits = format!("{digits:0>11}");
+        }
+
+        let values: Vec<u32> = digits.chars().filter_map(|ch| ch.to_digit(10)).collect();
+
+        let numero = match rural {
+            true => format!("P{digits}"),
+            false => digits,
+        };
+
+        match validate(uf, &values, rural) {
+            Ok(()) => Ok(InscricaoEstadual {
+                uf,
+                numero: Some(numero),
+            }),
+            Err(Falha::Length) => Err(InscricaoError::InvalidLength {
+                uf,
+                length: values.len(),
+            }),
+            Err(Falha::Prefix) => Err(InscricaoError::InvalidPrefix {
+                uf,
+                inscricao: numero,
+            }),
+            Err(Falha::CheckDigit) => Err(InscricaoError::InvalidCheckDigit {
+                uf,
+                inscricao: numero,
+            }),
+        }
+    }
+
+    /// State that issued the registration.
+    pub fn uf(&self) -> Uf {
+        self.uf
+    }
+
+    /// Exempt from the registration ("ISENTO").
+    pub fn is_isento(&self) -> bool {
+        self.numero.is_none()
+    }
+
+    /// The digits without the mask, or "ISENTO".
+    pub fn as_str(&self) -> &str {
+        self.numero.as_deref().unwrap_or(ISENTO)
+    }
+
+    /// The mask of the state, where `9` is a digit.
+    fn mask(&self) -> Option<&'static str> {
+        let numero = self.numero.as_deref()?;
+
+        let mask = match (self.uf, numero.len()) {
+            (Uf::SP, 13) => "P-99999999.9/999",
+            (Uf::AC, _) => "99.999.999/999-99",
+            (Uf::AM | Uf::GO | Uf::RN, 9) => "99.999.999-9",
+            (Uf::BA, 8) => "999999-99",
+            (Uf::BA, 9) => "9999999-99",
+            (Uf::CE | Uf::MS | Uf::PB | Uf::RR | Uf::SE | Uf::TO, 9) => "99999999-9",
+            (Uf::DF, _) => "99.999999.999-99",
+            (Uf::ES, _) => "999.999.99-9",
+            (Uf::MG, _) => "999.999.999/9999",
+            (Uf::MT, _) => "9999999999-9",
+            (Uf::PA, _) => "99-999999-9",
+            (Uf::PE, 9) => "9999999-99",
+            (Uf::PE, 14) => "99.9.999.9999999-9",
+            (Uf::PR, _) => "99999999-99",
+            (Uf::RJ, _) => "99.999.99-9",
+            (Uf::RN, 10) => "99.9.999.999-9",
+            (Uf::RO, 9) => "999.99999-9",
+            (Uf::RO, 14) => "9999999999999-9",
+            (Uf::RS, _) => "999/9999999",
+            (Uf::SC, _) => "999.999.999",
+            (Uf::SP, _) => "999.999.999.999",
+            (Uf::TO, 11) => "99.99.999999-9",
+            _ => return None,
+        };
+
+        Some(mask)
+    }
+}
+
+impl fmt::Display for InscricaoEstadual {
+    /// Format with the mask of the state.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(mask) = self.mask() else {
+            return write!(f, "{}", self.as_str());
+        };
+
+        let mut digits = self.as_str().chars().filter(char::is_ascii_digit);
+        let masked: String = mask
+            .chars()
+            .map(|ch| match ch {
+                '9' => digits.next().unwrap_or_default(),
+                _ => ch,
+            })
+            .collect();
+
+        write!(f, "{masked}")
+    }
+}
+
+/// Apply the rules of the state to the digits.
+fn validate(uf: Uf, d: &[u32], rural: bool) -> Result<(), Falha> {
+    let len = d.len();
+    let prefix = |prefixes: &[u32]| {
+        let value = d.get(..2).map_or(0, |p| p[0] * 10 + p[1]);
+        match prefixes.contains(&value) {
+            true => Ok(()),
+            false => Err(Falha::Prefix),
+        }
+    };
+    let number = |digits: &[u32]| digits.iter().fold(0, |n, &digit| n * 10 + digit);
+
+    let lengths: &[usize] = match uf {
+        Uf::AC | Uf::DF | Uf::MG => &[13],
+        Uf::BA => &[8, 9],
+        Uf::MT => &[11],
+        Uf::PE => &[9, 14],
+        Uf::PR | Uf::RS => &[10],
+        Uf::RJ => &[8],
+        Uf::RN => &[9, 10],
+        Uf::RO => &[9, 14],
+        Uf::SP => &[12],
+        Uf::TO => &[9, 11],
+        _ => &[9],
+    };
+
+    if !lengths.contains(&len) {
+        return Err(Falha::Length);
+    }
+
+    let valid = match uf {
+        Uf::AC | Uf::DF => {
+            prefix(match uf {
+                Uf::AC => &[1],
+                _ => &[7],
+            })?;
+            two_digits(AC_DF_DV1, AC_DF_DV2, d)
+        }
+        Uf::AL => {
+            prefix(&[24])?;
+            if ![0, 3, 5, 7, 8].contains(&d[2]) {
+                return Err(Falha::Prefix);
+            }
+            last_digit(mod11_vezes_10(PESOS_9_2), d)
+        }
+        Uf::AP => {
+            prefix(&[3])?;
+            let (p, rule) = match number(&d[..8]) {
+                3_000_001..=3_017_000 => (5, DigitRule::Complement { overflow: 0 }),
+                3_017_001..=3_019_022 => (9, AP_FAIXA),
+                _ => (0, DigitRule::Complement { overflow: 0 }),
+            };
+            let values: Vec<u32> = std::iter::once(p).chain(d[..8].iter().copied()).collect();
+            mod11(AP_PESOS).with_rule(rule).compute_values(&values) == d[8]
+        }
+        Uf::BA => {
+            // The last digit is computed first, then the previous one with it.
+            let base = &d[..len - 2];
+            let second = d[len - 8];
+            let check = |weights: &'static [u32], values: &[u32]| match second {
+                6 | 7 | 9 => mod11(weights).compute_values(values),
+                _ => CheckDigit::new(10, weights)
+                    .with_direction(Direction::LeftToRight)
+                    .compute_values(values),
+            };
+            let dv2 = check(&PESOS_9_2[8 - base.len()..], base);
+            let values: Vec<u32> = base.iter().copied().chain([dv2]).collect();
+            let dv1 = check(&PESOS_9_2[8 - values.len()..], &values);
+            dv1 == d[len - 2] && dv2 == d[len - 1]
+        }
+        Uf::GO => {
+            if !matches!(number(&d[..2]), 10 | 11 | 15 | 20..=29) {
+                return Err(Falha::Prefix);
+            }
+            match number(&d[..8]) {
+                11_094_402 => d[8] <= 1,
+                10_103_105..=10_119_997 => last_digit(GO_FAIXA, d),
+                _ => last_digit(PADRAO, d),
+            }
+        }
+        Uf::MA => {
+            prefix(&[12])?;
+            last_digit(PADRAO, d)
+        }
+        Uf::MS => {
+            prefix(&[28, 50])?;
+            last_digit(PADRAO, d)
+        }
+        Uf::MG => {
+            // A zero is inserted after the municipality code (3 digits).
+            let values: Vec<u32> = d[..3]
+                .iter()
+                .chain(&[0])
+                .chain(&d[3..11])
+                .copied()
+                .collect();
+            MG_DV1.compute_values(&values) == d[11] && last_digit(MG_DV2, d)
+        }
+        Uf::MT => last_digit(MT, d),
+        Uf::PA => {
+            prefix(&[15])?;
+            last_digit(PADRAO, d)
+        }
+        Uf::PE => match len {
+            9 => two_digits(mod11(&PESOS_9_2[1..]), PADRAO, d),
+            _ => last_digit(mod11_menos_10(&[5, 4, 3, 2, 1, 9, 8, 7, 6, 5, 4, 3, 2]), d),
+        },
+        Uf::PR => two_digits(PR_DV1, PR_DV2, d),
+        Uf::RJ => last_digit(RJ, d),
+        Uf::RN => {
+            prefix(&[20])?;
+            match len {
+                9 => last_digit(mod11_vezes_10(PESOS_9_2), d),
+                _ => last_digit(mod11_vezes_10(&[10, 9, 8, 7, 6, 5, 4, 3, 2]), d),
+            }
+        }
+        Uf::RO => match len {
+            // Old format: the municipality code (3 digits) is not weighted.
+            9 => mod11_menos_10(&[6, 5, 4, 3, 2]).compute_values(&d[3..8]) == d[8],
+            _ => last_digit(mod11_menos_10(&[6, 5, 4, 3, 2, 9, 8, 7, 6, 5, 4, 3, 2]), d),
+        },
+        Uf::RR => {
+            prefix(&[24])?;
+            last_digit(RR, d)
+        }
+        Uf::RS => last_digit(RS, d),
+        Uf::SP => SP_DV1.compute_values(&d[..8]) == d[8] && (rural || last_digit(SP_DV2, d)),
+        Uf::TO if len == 11 => {
+            // Old format: the type of company (2 digits) is not weighted.
+            if !matches!(number(&d[2..4]), 1 | 2 | 3 | 99) {
+                return Err(Falha::Prefix);
+            }
+            let values: Vec<u32> = d[..2].iter().chain(&d[4..10]).copied().collect();
+            PADRAO.compute_values(&values) == d[10]
+        }
+        Uf::AM | Uf::CE | Uf::ES | Uf::PB | Uf::PI | Uf::SC | Uf::SE | Uf::TO => {
+            last_digit(PADRAO, d)
+        }
+    };
+
+    match valid {
+        true => Ok(()),
+        false => Err(Falha::CheckDigit),
+    }
+}
+
+/// The last digit is the check digit of the others.
+fn last_digit(check: CheckDigit, d: &[u32]) -> bool {
+    let (base, dv) = d.split_at(d.len() - 1);
+    check.compute_values(base) == dv[0]
+}
+
+/// The last two digits are the check digits of the previous ones.
+fn two_digits(dv1: CheckDigit, dv2: CheckDigit, d: &[u32]) -> bool {
+    last_digit(dv1, &d[..d.len() - 1]) && last_digit(dv2, d)
+}
+
+#[cfg(test)]
+mod inscricao_estadual_tests {
+    use super::*;
+
+    // cargo test -- --show-output inscricao_estadual_tests
+
+    /// Examples of the SINTEGRA page of each state.
+    const VALIDAS: [(Uf, &str, &str); 33] = [
+        (Uf::AC, "0100482300112", "01.004.823/001-12"),
+        (Uf::AL, "240000048", "240000048"),
+        (Uf::AP, "030123459", "030123459"),
+        (Uf::AM, "042933684", "04.293.368-4"),
+        (Uf::BA, "12345663", "123456-63"),
+        (Uf::BA, "61234557", "612345-57"),
+        (Uf::BA, "100000306", "1000003-06"),
+        (Uf::CE, "060000015", "06000001-5"),
+        (Uf::DF, "0730000100109", "07.300001.001-09"),
+        (Uf::ES, "999999990", "999.999.99-0"),
+        (Uf::GO, "109876547", "10.987.654-7"),
+        (Uf::MA, "120000385", "120000385"),
+        (Uf::MT, "00130000019", "0013000001-9"),
+        (Uf::MS, "283115947", "28311594-7"),
+        (Uf::MG, "0623079040081", "062.307.904/0081"),
+        (Uf::PA, "159999995", "15-999999-5"),
+        (Uf::PB, "060000015", "06000001-5"),
+        (Uf::PR, "1234567850", "12345678-50"),
+        (Uf::PE, "032141840", "0321418-40"),
+        (Uf::PE, "18100100000049", "18.1.001.0000004-9"),
+        (Uf::PI, "012345679", "012345679"),
+        (Uf::RJ, "99999993", "99.999.99-3"),
+        (Uf::RN, "200400401", "20.040.040-1"),
+        (Uf::RN, "2000400400", "20.0.040.040-0"),
+        (Uf::RS, "2243658792", "224/3658792"),
+        (Uf::RO, "101625213", "101.62521-3"),
+        (Uf::RO, "00000000625213", "0000000062521-3"),
+        (Uf::RR, "240066281", "24006628-1"),
+        (Uf::SC, "251040852", "251.040.852"),
+        (Uf::SP, "110042490114", "110.042.490.114"),
+        (Uf::SP, "P011004243002", "P-01100424.3/002"),
+        (Uf::SE, "271234563", "27123456-3"),
+        (Uf::TO, "29010227836", "29.01.022783-6"),
+    ];
+
+    #[test]
+    fn valid_examples() {
+        for (uf, inscricao, masked) in VALIDAS {
+            let ie = InscricaoEstadual::new(uf, inscricao)
+                .unwrap_or_else(|error| panic!("{uf} {inscricao}: {error}"));
+            assert_eq!(ie.as_str(), inscricao);
+            assert_eq!(ie.to_string(), masked, "{uf}");
+            assert_eq!(InscricaoEstadual::new(uf, masked).as_ref(), Ok(&ie));
+        }
+
+        let ie = InscricaoEstadual::new(Uf::MT, "130000019").unwrap();
+        assert_eq!(ie.as_str(), "00130000019");
+    }
+
+    #[test]
+    fn wrong_check_digits() {
+        // The last digits of SP rural producers are not check digits.
+        for &(uf, inscricao, _) in VALIDAS.iter().filter(|(_, ie, _)| !ie.starts_with('P')) {
+            let last = inscricao.len() - 1;
+            let digit = inscricao[last..].parse::<u32>().unwrap();
+            let changed = format!("{}{}", &inscricao[..last], (digit + 1) % 10);
+
+            let error = InscricaoEstadual::new(uf, &changed).unwrap_err();
+            assert!(
+                matches!(error, InscricaoError::InvalidCheckDigit { .. }),
+                "{uf} {changed}: {error}"
+            );
+        }
+
+        assert!(InscricaoEstadual::new(Uf::SP, "P-01100424.4/002").is_err());
+    }
+
+    #[test]
+    fn invalid_formats() {
+        assert!(InscricaoEstadual::new(Uf::SC, "Isento")
+            .unwrap()
+            .is_isento());
+        assert_eq!(
+            InscricaoEstadual::new(Uf::SC, " "),
+            Err(InscricaoError::Empty)
+        );
+        assert_eq!(
+            InscricaoEstadual::new(Uf::SC, "251A40852"),
+            Err(InscricaoError::InvalidCharacter('A'))
+        );
+        assert_eq!(
+            InscricaoEstadual::new(Uf::RJ, "P011004243002"),
+            Err(InscricaoError::InvalidCharacter('P'))
+        );
+        assert_eq!(
+            InscricaoEstadual::new(Uf::SP, "11004249011"),
+            Err(InscricaoError::InvalidLength {
+                uf: Uf::SP,
+                length: 11
+            })
+        );
+        assert_eq!(
+            InscricaoEstadual::new(Uf::PA, "259999995"),
+            Err(InscricaoError::InvalidPrefix {
+                uf: Uf::PA,
+                inscricao: "259999995".to_string()
+            })
+        );
+    }
+}
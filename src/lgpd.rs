@@ -0,0 +1,325 @@
+use crate::{Cnpj, Cpf, StrExtension};
+use blake3::{Hasher, OutputReader};
+
+/// Context of [`Pseudonimizador::from_passphrase`], as required by `blake3::derive_key`.
+const KEY_CONTEXT: &str = "claudiofsr_lib 2024-01-01 LGPD pseudonymization key";
+
+/// Words of a name that are not abbreviated by [`mascarar_nome`].
+const CONECTIVOS: [&str; 6] = ["da", "das", "de", "do", "dos", "e"];
+
+/**
+Mask a CPF following the standard of the federal government:
+only the digits 4 to 9 are shown.
+
+```
+    use claudiofsr_lib::{mascarar_cpf, Cpf};
+
+    let cpf: Cpf = "529.982.247-25".parse().unwrap();
+    assert_eq!(mascarar_cpf(&cpf), "***.982.247-**");
+```
+*/
+pub fn mascarar_cpf(cpf: &Cpf) -> String {
+    let s = cpf.as_str();
+    format!("***.{}.{}-**", &s[3..6], &s[6..9])
+}
+
+/**
+Mask a CNPJ like [`mascarar_cpf`]: the first 2 characters and the check digits
+are hidden, the branch (filial) is shown.
+
+```
+    use claudiofsr_lib::{mascarar_cnpj, Cnpj};
+
+    let cnpj: Cnpj = "11.222.333/0001-81".parse().unwrap();
+    assert_eq!(mascarar_cnpj(&cnpj), "**.222.333/0001-**");
+```
+*/
+pub fn mascarar_cnpj(cnpj: &Cnpj) -> String {
+    let s = cnpj.as_str();
+    format!("**.{}.{}/{}-**", &s[2..5], &s[5..8], cnpj.filial())
+}
+
+/**
+Mask an e-mail: the first character of the user and the domain are shown.
+
+Text without `@` is fully masked.
+
+```
+    use claudiofsr_lib::mascarar_email;
+
+    assert_eq!(mascarar_email("fulano.tal@example.com"), "f*********@example.com");
+    assert_eq!(mascarar_email("invalid"), "*******");
+```
+*/
+pub fn mascarar_email(email: &str) -> String {
+    let email = email.trim();
+
+    match email.split_once('@') {
+        Some((user, domain)) if !user.is_empty() => {
+            let mut chars = user.chars();
+            let first = chars.next().unwrap_or_default();
+            let hidden = "*".repeat(chars.count());
+            format!("{first}{hidden}@{domain}")
+        }
+        _ => "*".repeat(email.chars().count()),
+    }
+}
+
+/**
+Mask a name: the first name is shown and the other words are abbreviated,
+except the connectives ("da", "de", "dos", ...) that are removed.
+
+```
+    use claudiofsr_lib::mascarar_nome;
+
+    assert_eq!(mascarar_nome("Maria  da Conceição Souza"), "Maria C. S.");
+    assert_eq!(mascarar_nome("João"), "João");
+```
+*/
+pub fn mascarar_nome(nome: &str) -> String {
+    let mut words = nome.split_whitespace();
+
+    let Some(first) = words.next() else {
+        return String::new();
+    };
+
+    words
+        .filter(|word| !CONECTIVOS.contains(&word.to_lowercase().as_str()))
+        .filter_map(|word| word.chars().next())
+        .fold(first.to_string(), |mut masked, initial| {
+            masked.push(' ');
+            masked.push(initial);
+            masked.push('.');
+            masked
+        })
+}
+
+/**
+Keyed deterministic pseudonymization of personal data.
+
+The same value with the same key always gives the same pseudonym, so joins
+across files still work after anonymization. Without the key, the original
+values cannot be recovered or tested.
+
+The pseudonyms keep the format: CPF and CNPJ have valid check digits,
+the CNPJ keeps its branch (filial), and names keep the number and length
+of the words.
+
+Different values may give the same pseudonym, with a probability of about
+n² / 2·10⁹ for n CPFs.
+
+Example:
+```
+    use claudiofsr_lib::{Cnpj, Cpf, Pseudonimizador};
+
+    let pseudonimizador = Pseudonimizador::from_passphrase("audit 2024 secret");
+
+    let cpf: Cpf = "529.982.247-25".parse().unwrap();
+    let pseudo = pseudonimizador.cpf(&cpf);
+    assert_ne!(pseudo, cpf);
+    assert_eq!(pseudo, pseudonimizador.cpf(&cpf));
+    assert!(Cpf::parse(pseudo.as_str()).is_ok());
+
+    let matriz: Cnpj = "11.222.333/0001-81".parse().unwrap();
+    let filial: Cnpj = "11.222.333/0002-62".parse().unwrap();
+    let (a, b) = (pseudonimizador.cnpj(&matriz), pseudonimizador.cnpj(&filial));
+    assert_eq!(a.raiz(), b.raiz());
+    assert_eq!(b.filial(), "0002");
+
+    let nome = pseudonimizador.nome("José da Silva");
+    assert_eq!(nome, pseudonimizador.nome("Jose  da Silva"));
+    assert_eq!(nome.len(), "Jose da Silva".len());
+```
+*/
+#[derive(Clone)]
+pub struct Pseudonimizador {
+    key: [u8; blake3::KEY_LEN],
+}
+
+impl Pseudonimizador {
+    pub const fn new(key: [u8; blake3::KEY_LEN]) -> Self {
+        Pseudonimizador { key }
+    }
+
+    /// Derive the key from a passphrase.
+    pub fn from_passphrase(passphrase: &str) -> Self {
+        Pseudonimizador::new(blake3::derive_key(KEY_CONTEXT, passphrase.as_bytes()))
+    }
+
+    /// CPF with valid check digits.
+    pub fn cpf(&self, cpf: &Cpf) -> Cpf {
+        let mut reader = self.reader("cpf", cpf.as_str());
+
+        loop {
+            let base: String = (0..9).map(|_| draw(&mut reader, DIGITS)).collect();
+            if let Ok(cpf) = Cpf::from_base(&base) {
+                return cpf;
+            }
+        }
+    }
+
+    /// CNPJ with valid check digits and the same branch (filial).
+    ///
+    /// The root (raiz) is alphanumeric only if the original one is.
+    pub fn cnpj(&self, cnpj: &Cnpj) -> Cnpj {
+        let mut reader = self.reader("cnpj", cnpj.raiz());
+        let alphabet = match cnpj.raiz().bytes().any(|b| b.is_ascii_alphabetic()) {
+            true => ALPHANUMERIC,
+            false => DIGITS,
+        };
+
+        loop {
+            let raiz: String = (0..8).map(|_| draw(&mut reader, alphabet)).collect();
+            if let Ok(pseudo) = Cnpj::from_base(&format!("{raiz}{}", cnpj.filial())) {
+                return pseudo;
+            }
+        }
+    }
+
+    /**
+    Name with the same number and length of words, and the same case.
+
+    Accents, case and repeated whitespace are ignored, so "José  da Silva"
+    and "JOSE DA SILVA" give the same letters.
+    */
+    pub fn nome(&self, nome: &str) -> String {
+        let normalized = nome.remove_accents().to_uppercase();
+        let mut reader = self.reader("nome", &normalized.replace_multiple_whitespaces());
+
+        nome.split_whitespace()
+            .map(|word| {
+                word.chars()
+                    .map(|ch| match ch {
+                        c if c.is_uppercase() => draw(&mut reader, UPPERCASE),
+                        c if c.is_alphabetic() => draw(&mut reader, UPPERCASE).to_ascii_lowercase(),
+                        c => c,
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// E-mail with a pseudonymous user of the same length and the same domain.
+    ///
+    /// Case is ignored. Text without `@` is entirely replaced.
+    pub fn email(&self, email: &str) -> String {
+        let email = email.trim().to_lowercase();
+        let mut reader = self.reader("email", &email);
+
+        let (user, domain) = match email.split_once('@') {
+            Some((user, domain)) => (user, Some(domain)),
+            None => (email.as_str(), None),
+        };
+
+        let pseudo: String = user
+            .chars()
+            .map(|_| draw(&mut reader, ALPHANUMERIC).to_ascii_lowercase())
+            .collect();
+
+        match domain {
+            Some(domain) => format!("{pseudo}@{domain}"),
+            None => pseudo,
+        }
+    }
+
+    /// Stream of bytes of the keyed hash of the value, separated by kind.
+    fn reader(&self, kind: &str, value: &str) -> OutputReader {
+        let mut hasher = Hasher::new_keyed(&self.key);
+        hasher.update(kind.as_bytes());
+        hasher.update(&[0]);
+        hasher.update(value.as_bytes());
+        hasher.finalize_xof()
+    }
+}
+
+impl std::fmt::Debug for Pseudonimizador {
+    /// The key is not shown.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Pseudonimizador").finish_non_exhaustive()
+    }
+}
+
+const DIGITS: &[u8] = b"0123456789";
+const UPPERCASE: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const ALPHANUMERIC: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Uniform character of the alphabet, rejecting the bytes that would bias it.
+fn draw(reader: &mut OutputReader, alphabet: &[u8]) -> char {
+    let limit = 256 - 256 % alphabet.len();
+    let mut byte = [0];
+
+    loop {
+        reader.fill(&mut byte);
+        let value = usize::from(byte[0]);
+        if value < limit {
+            return alphabet[value % alphabet.len()] as char;
+        }
+    }
+}
+
+#[cfg(test)]
+mod lgpd_tests {
+    use super::*;
+
+    // cargo test -- --show-output lgpd_tests
+
+    #[test]
+    fn masks() {
+        let cpf: Cpf = "111.444.777-35".parse().unwrap();
+        assert_eq!(mascarar_cpf(&cpf), "***.444.777-**");
+
+        let cnpj: Cnpj = "12.ABC.345/01DE-35".parse().unwrap();
+        assert_eq!(mascarar_cnpj(&cnpj), "**.ABC.345/01DE-**");
+
+        assert_eq!(mascarar_email(" ana@example.com "), "a**@example.com");
+        assert_eq!(mascarar_email("@example.com"), "************");
+        assert_eq!(mascarar_nome(" Ana de Souza e Silva "), "Ana S. S.");
+        assert_eq!(mascarar_nome(""), "");
+    }
+
+    #[test]
+    fn deterministic_and_keyed() {
+        let a = Pseudonimizador::from_passphrase("key a");
+        let b = Pseudonimizador::from_passphrase("key b");
+        let cpf: Cpf = "529.982.247-25".parse().unwrap();
+
+        assert_eq!(a.cpf(&cpf), a.cpf(&cpf));
+        assert_ne!(a.cpf(&cpf), b.cpf(&cpf));
+        assert_eq!(a.email("Ana@Example.com"), a.email("ana@example.com"));
+        assert_ne!(a.email("ana@example.com"), b.email("ana@example.com"));
+        assert!(!format!("{a:?}").contains("key"));
+
+        let cpfs: Vec<Cpf> = ["111.444.777-35", "529.982.247-25", "123.456.789-09"]
+            .iter()
+            .map(|text| a.cpf(&text.parse().unwrap()))
+            .collect();
+        assert_ne!(cpfs[0], cpfs[1]);
+        assert_ne!(cpfs[1], cpfs[2]);
+    }
+
+    #[test]
+    fn keeps_format() {
+        let pseudonimizador = Pseudonimizador::new([7; blake3::KEY_LEN]);
+
+        let cnpj: Cnpj = "12.ABC.345/01DE-35".parse().unwrap();
+        let pseudo = pseudonimizador.cnpj(&cnpj);
+        assert_eq!(pseudo.filial(), "01DE");
+        assert_eq!(Cnpj::parse(pseudo.as_str()), Ok(pseudo));
+
+        let cnpj: Cnpj = "11.222.333/0001-81".parse().unwrap();
+        assert!(!pseudonimizador.cnpj(&cnpj).is_alphanumeric());
+
+        let nome = pseudonimizador.nome("Maria d'Ávila Gonçalves-Lima");
+        let words: Vec<usize> = nome.split(' ').map(|w| w.chars().count()).collect();
+        assert_eq!(words, [5, 7, 14]);
+        assert!(nome.is_ascii());
+        assert_eq!(nome.chars().nth(7), Some('\''));
+        assert_eq!(nome.chars().nth(23), Some('-'));
+        assert!(nome.starts_with(|ch: char| ch.is_ascii_uppercase()));
+
+        let email = pseudonimizador.email("fulano@example.com");
+        assert_eq!(email.len(), "fulano@example.com".len());
+        assert!(email.ends_with("@example.com"));
+    }
+}
@@ -13,23 +13,52 @@ use std::{
     str,
 };
 
+mod boleto;
+mod calendar;
+mod cfop;
+mod chave_de_acesso;
+mod check_digit;
+mod classifier;
+mod cnpj;
 mod constants;
+mod cpf;
+mod cst;
+mod date_parser;
+mod decimal;
+mod extenso;
+mod inscricao_estadual;
 mod iterations;
+mod lgpd;
 mod macros;
 mod maps;
+mod municipio;
+mod natureza_bc;
+mod number_format;
+mod number_parser;
 mod operations;
 mod options;
+mod periodo_apuracao;
+mod pix;
 mod random;
 mod rounded;
 mod separator;
 mod slice;
+mod sped_reader;
+mod sped_record;
+mod sped_validator;
+mod sped_writer;
 mod strings;
 mod traits;
+mod uf;
 mod unique;
 
 pub use self::{
-    constants::*, iterations::*, macros::*, maps::*, operations::*, options::*, random::*,
-    rounded::*, separator::*, slice::*, strings::*, traits::*, unique::*,
+    boleto::*, calendar::*, cfop::*, chave_de_acesso::*, check_digit::*, classifier::*, cnpj::*,
+    constants::*, cpf::*, cst::*, date_parser::*, decimal::*, extenso::*, inscricao_estadual::*,
+    iterations::*, lgpd::*, macros::*, maps::*, municipio::*, natureza_bc::*, number_format::*,
+    number_parser::*, operations::*, options::*, periodo_apuracao::*, pix::*, random::*,
+    rounded::*, separator::*, slice::*, sped_reader::*, sped_record::*, sped_validator::*,
+    sped_writer::*, strings::*, traits::*, uf::*, unique::*,
 };
 
 pub type MyError = Box<dyn std::error::Error + Send + Sync>;
@@ -235,6 +264,8 @@ where
 ///
 /// Returns None on the out-of-range date, invalid month and/or day.
 ///
+/// See [`DateParser`] for other formats and errors describing the failure.
+///
 /// <https://docs.rs/chrono/latest/chrono/naive/struct.NaiveDate.html#method.from_ymd_opt>
 ///
 /// <https://docs.rs/chrono/latest/chrono/struct.DateTime.html#method.parse_from_str>
@@ -268,16 +299,7 @@ where
         return None;
     };
 
-    match NaiveDate::parse_from_str(ddmmyyyy, "%-d%-m%Y") {
-        Ok(dt) => Some(dt),
-        Err(why) => {
-            eprintln!("fn get_naive_date()");
-            eprintln!("Data inválida ou inexistente!");
-            eprintln!("Erro: {why}");
-            eprintln!("\t'{date}'");
-            None
-        }
-    }
+    DateParser::new().parse(ddmmyyyy).ok()
 }
 
 /// Gets Date from a string containing 8 digits.
@@ -289,9 +311,7 @@ where
 
     // date: DDMMYYYY
     let ddmmyyyy: u32 = if digits.chars_count() >= 8 {
-        digits[..8]
-            .parse::<u32>()
-            .expect("fn get_naive_date()\nEsperado um número inteiro com 8 dígitos!")
+        digits[..8].parse::<u32>().ok()?
     } else {
         return None;
     };
@@ -302,14 +322,7 @@ where
     let month = mmyyyy / 10_000;
     let year = mmyyyy % 10_000;
 
-    let dt: Option<NaiveDate> = NaiveDate::from_ymd_opt(year as i32, month, day);
-
-    if dt.is_none() {
-        eprintln!("Erro! Data inválida ou inexistente:");
-        eprintln!("\t'{date}': day: {day} ; month: {month} ; year: {year}");
-    }
-
-    dt
+    NaiveDate::from_ymd_opt(year as i32, month, day)
 }
 
 // https://stackoverflow.com/questions/26536871/how-can-i-convert-a-string-of-numbers-to-an-array-or-vector-of-integers-in-rust
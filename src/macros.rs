@@ -1,5 +1,6 @@
 pub mod svec {
     #![macro_use]
+    #![allow(clippy::empty_line_after_outer_attr)]
     #[macro_export]
 
     /**
@@ -65,6 +66,7 @@ pub mod svec {
 
 pub mod match_cast {
     #![macro_use]
+    #![allow(clippy::empty_line_after_outer_attr)]
     #[macro_export]
 
     /**
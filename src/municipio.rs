@@ -0,0 +1,360 @@
+use crate::{CheckDigit, Uf};
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+/// Municipalities whose IBGE code does not follow the mod-10 check digit.
+///
+/// They are accepted by the SEFAZ validation of the NF-e.
+const CODIGOS_SEM_DV: [u32; 9] = [
+    2201919, // Bom Princípio do Piauí/PI
+    2201988, // Brejo do Piauí/PI
+    2202251, // Canavieira/PI
+    2611533, // Quixaba/PE
+    3117836, // Cônego Marinho/MG
+    3152131, // Ponto Chique/MG
+    4305871, // Coronel Barros/RS
+    5203939, // Buriti de Goiás/GO
+    5203962, // Buritinópolis/GO
+];
+
+/// The state capitals, bundled name table of [`Municipios::capitais`].
+const CAPITAIS: [(u32, &str); 27] = [
+    (1100205, "Porto Velho"),
+    (1200401, "Rio Branco"),
+    (1302603, "Manaus"),
+    (1400100, "Boa Vista"),
+    (1501402, "Belém"),
+    (1600303, "Macapá"),
+    (1721000, "Palmas"),
+    (2111300, "São Luís"),
+    (2211001, "Teresina"),
+    (2304400, "Fortaleza"),
+    (2408102, "Natal"),
+    (2507507, "João Pessoa"),
+    (2611606, "Recife"),
+    (2704302, "Maceió"),
+    (2800308, "Aracaju"),
+    (2927408, "Salvador"),
+    (3106200, "Belo Horizonte"),
+    (3205309, "Vitória"),
+    (3304557, "Rio de Janeiro"),
+    (3550308, "São Paulo"),
+    (4106902, "Curitiba"),
+    (4205407, "Florianópolis"),
+    (4314902, "Porto Alegre"),
+    (5002704, "Campo Grande"),
+    (5103403, "Cuiabá"),
+    (5208707, "Goiânia"),
+    (5300108, "Brasília"),
+];
+
+/// Errors returned when parsing a municipality code or a name table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MunicipioError {
+    /// Not 7 digits.
+    InvalidFormat(String),
+    /// The first two digits are not an IBGE state code.
+    InvalidUf(u8),
+    InvalidCheckDigit {
+        expected: u8,
+        found: u8,
+    },
+    /// Line of a name table without a code and a name.
+    InvalidLine {
+        line_number: usize,
+        line: String,
+    },
+}
+
+impl fmt::Display for MunicipioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MunicipioError::InvalidFormat(codigo) => {
+                write!(f, "municipality code must have 7 digits: {codigo:?}")
+            }
+            MunicipioError::InvalidUf(codigo) => {
+                write!(f, "invalid UF code in municipality code: {codigo}")
+            }
+            MunicipioError::InvalidCheckDigit { expected, found } => {
+                write!(
+                    f,
+                    "invalid municipality code check digit: expected {expected}, found {found}"
+                )
+            }
+            MunicipioError::InvalidLine { line_number, line } => {
+                write!(
+                    f,
+                    "line {line_number}: expected a code and a name: '{line}'"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for MunicipioError {}
+
+/**
+IBGE 7-digit municipality code (cMun, COD_MUN): state (2), municipality (4)
+and a mod-10 check digit.
+
+Example:
+```
+    use claudiofsr_lib::{CodigoMunicipio, MunicipioError, Uf};
+
+    let codigo: CodigoMunicipio = "3550308".parse().unwrap();
+
+    assert_eq!(codigo.uf(), Uf::SP);
+    assert_eq!(codigo.digito_verificador(), 8);
+    assert_eq!(codigo.nome(), Some("São Paulo"));
+    assert_eq!(codigo.descricao(), "3550308 - São Paulo/SP");
+
+    assert_eq!(
+        CodigoMunicipio::new(3550307),
+        Err(MunicipioError::InvalidCheckDigit { expected: 8, found: 7 })
+    );
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CodigoMunicipio(u32);
+
+impl CodigoMunicipio {
+    pub fn new(codigo: u32) -> Result<Self, MunicipioError> {
+        if !(1_000_000..=9_999_999).contains(&codigo) {
+            return Err(MunicipioError::InvalidFormat(codigo.to_string()));
+        }
+
+        let codigo_uf = (codigo / 100_000) as u8;
+        if Uf::from_codigo(codigo_uf).is_none() {
+            return Err(MunicipioError::InvalidUf(codigo_uf));
+        }
+
+        let expected = CheckDigit::MOD10
+            .compute(&(codigo / 10).to_string())
+            .expect("six digits") as u8;
+        let found = (codigo % 10) as u8;
+
+        if expected != found && !CODIGOS_SEM_DV.contains(&codigo) {
+            return Err(MunicipioError::InvalidCheckDigit { expected, found });
+        }
+
+        Ok(CodigoMunicipio(codigo))
+    }
+
+    pub const fn codigo(&self) -> u32 {
+        self.0
+    }
+
+    /// State given by the first two digits.
+    pub fn uf(&self) -> Uf {
+        Uf::from_codigo((self.0 / 100_000) as u8).expect("validated on construction")
+    }
+
+    pub const fn digito_verificador(&self) -> u8 {
+        (self.0 % 10) as u8
+    }
+
+    /// Name in the bundled table, which only has the 27 state capitals.
+    ///
+    /// For the other municipalities, load the IBGE table with
+    /// [`Municipios::parse_municipios`] and use [`Municipios::nome`].
+    pub fn nome(&self) -> Option<&'static str> {
+        CAPITAIS
+            .iter()
+            .find(|(codigo, _)| *codigo == self.0)
+            .map(|(_, nome)| *nome)
+    }
+
+    /// "3550308 - São Paulo/SP", or "3550308/SP" outside the state capitals.
+    ///
+    /// For the other municipalities, use [`Municipios::descricao`].
+    pub fn descricao(&self) -> String {
+        self.descricao_com_nome(self.nome())
+    }
+
+    /// "3550308 - São Paulo/SP", or "3550308/SP" without a name.
+    fn descricao_com_nome(&self, nome: Option<&str>) -> String {
+        match nome {
+            Some(nome) => format!("{} - {nome}/{}", self.0, self.uf()),
+            None => format!("{}/{}", self.0, self.uf()),
+        }
+    }
+}
+
+impl TryFrom<u32> for CodigoMunicipio {
+    type Error = MunicipioError;
+
+    fn try_from(codigo: u32) -> Result<Self, Self::Error> {
+        CodigoMunicipio::new(codigo)
+    }
+}
+
+impl FromStr for CodigoMunicipio {
+    type Err = MunicipioError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = s.trim();
+
+        if text.len() != 7 || !text.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(MunicipioError::InvalidFormat(text.to_string()));
+        }
+
+        CodigoMunicipio::new(text.parse().expect("7 digits"))
+    }
+}
+
+impl fmt::Display for CodigoMunicipio {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/**
+Table of municipality names, for codes outside the bundled capitals.
+
+Each line has the code and the name separated by `;`, a tab or spaces,
+like the IBGE spreadsheet exported as CSV.
+Blank lines and lines starting with `#` are ignored.
+
+Example:
+```
+    use claudiofsr_lib::{CodigoMunicipio, Municipios};
+
+    let municipios = Municipios::capitais()
+        .parse_municipios("3509502;Campinas\n4113700\tLondrina")
+        .unwrap();
+
+    let campinas: CodigoMunicipio = "3509502".parse().unwrap();
+    assert_eq!(municipios.descricao(campinas), "3509502 - Campinas/SP");
+    assert_eq!(municipios.len(), 29);
+```
+*/
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Municipios {
+    nomes: BTreeMap<CodigoMunicipio, String>,
+}
+
+impl Municipios {
+    /// Empty table.
+    pub fn new() -> Self {
+        Municipios::default()
+    }
+
+    /// Table with the 27 state capitals.
+    pub fn capitais() -> Self {
+        CAPITAIS
+            .iter()
+            .fold(Municipios::new(), |municipios, &(codigo, nome)| {
+                let codigo = CodigoMunicipio::new(codigo).expect("valid capital code");
+                municipios.with_municipio(codigo, nome)
+            })
+    }
+
+    pub fn with_municipio(mut self, codigo: CodigoMunicipio, nome: &str) -> Self {
+        self.nomes.insert(codigo, nome.trim().to_string());
+        self
+    }
+
+    /// Add the names of the text, one municipality per line.
+    pub fn parse_municipios(mut self, text: &str) -> Result<Self, MunicipioError> {
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            let is_separator = |ch: char| ch == ';' || ch.is_whitespace();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (codigo, nome) = line
+                .split_once(is_separator)
+                .map(|(codigo, nome)| (codigo, nome.trim_matches(is_separator)))
+                .filter(|(_, nome)| !nome.is_empty())
+                .ok_or_else(|| MunicipioError::InvalidLine {
+                    line_number: index + 1,
+                    line: line.to_string(),
+                })?;
+
+            self = self.with_municipio(codigo.parse()?, nome);
+        }
+
+        Ok(self)
+    }
+
+    pub fn nome(&self, codigo: CodigoMunicipio) -> Option<&str> {
+        self.nomes.get(&codigo).map(String::as_str)
+    }
+
+    /// "3550308 - São Paulo/SP", or "3550308/SP" if the name is unknown.
+    pub fn descricao(&self, codigo: CodigoMunicipio) -> String {
+        codigo.descricao_com_nome(self.nome(codigo))
+    }
+
+    pub fn len(&self) -> usize {
+        self.nomes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nomes.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod municipio_tests {
+    use super::*;
+
+    // cargo test -- --show-output municipio_tests
+
+    #[test]
+    fn check_digit() {
+        for (codigo, _) in CAPITAIS {
+            assert!(CodigoMunicipio::new(codigo).is_ok(), "{codigo}");
+        }
+        for codigo in CODIGOS_SEM_DV {
+            assert!(CodigoMunicipio::new(codigo).is_ok(), "{codigo}");
+        }
+
+        assert_eq!(
+            CodigoMunicipio::new(2201910),
+            Err(MunicipioError::InvalidCheckDigit {
+                expected: 1,
+                found: 0
+            })
+        );
+        assert_eq!(
+            CodigoMunicipio::new(2001000),
+            Err(MunicipioError::InvalidUf(20))
+        );
+        for text in ["355030", "35503088", "355030a", "+355030"] {
+            assert_eq!(
+                text.parse::<CodigoMunicipio>(),
+                Err(MunicipioError::InvalidFormat(text.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn states_and_names() {
+        let brasilia: CodigoMunicipio = " 5300108 ".parse().unwrap();
+        assert_eq!(brasilia.uf(), Uf::DF);
+        assert_eq!(brasilia.descricao(), "5300108 - Brasília/DF");
+
+        let quixaba = CodigoMunicipio::new(2611533).unwrap();
+        assert_eq!(quixaba.uf(), Uf::PE);
+        assert_eq!(quixaba.nome(), None);
+        assert_eq!(quixaba.descricao(), "2611533/PE");
+
+        let municipios = Municipios::new()
+            .parse_municipios("# PE\n2611533 ; Quixaba\n\n2611606 Recife")
+            .unwrap();
+        assert_eq!(municipios.nome(quixaba), Some("Quixaba"));
+        assert_eq!(municipios.descricao(quixaba), "2611533 - Quixaba/PE");
+        assert_eq!(municipios.len(), 2);
+        assert_eq!(Municipios::capitais().len(), 27);
+
+        assert_eq!(
+            Municipios::new().parse_municipios("2611533"),
+            Err(MunicipioError::InvalidLine {
+                line_number: 1,
+                line: "2611533".to_string()
+            })
+        );
+    }
+}
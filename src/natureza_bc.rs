@@ -0,0 +1,271 @@
+use std::{fmt, str::FromStr};
+
+/// Errors returned when converting a code to [`NaturezaBaseCalculo`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NaturezaBcError {
+    /// The text is not a number with up to 2 digits.
+    InvalidFormat(String),
+    /// The number is not between 1 and 18.
+    InvalidCode(u16),
+}
+
+impl fmt::Display for NaturezaBcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NaturezaBcError::InvalidFormat(nat) => {
+                write!(f, "base calculation code must have up to 2 digits: {nat:?}")
+            }
+            NaturezaBcError::InvalidCode(code) => {
+                write!(f, "invalid base calculation code: {code}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for NaturezaBcError {}
+
+/**
+Código da Base de Cálculo do Crédito (NAT_BC_CRED) of PIS/PASEP and COFINS.
+
+Official table 4.3.7 of EFD-Contribuições.
+
+Example:
+```
+    use claudiofsr_lib::NaturezaBaseCalculo;
+
+    let nat: NaturezaBaseCalculo = "02".parse().unwrap();
+    assert_eq!(nat, NaturezaBaseCalculo::AquisicaoDeBensInsumo);
+    assert_eq!(nat.code(), 2);
+    assert_eq!(nat.to_string(), "02");
+    assert_eq!(nat.descricao(), "Aquisição de bens utilizados como insumo");
+    assert!(nat.is_aquisicao());
+
+    let nat = NaturezaBaseCalculo::try_from(9).unwrap();
+    assert!(nat.is_depreciacao());
+    assert!(nat.is_ativo_imobilizado());
+
+    assert!(NaturezaBaseCalculo::try_from(19).is_err());
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[repr(u16)]
+pub enum NaturezaBaseCalculo {
+    AquisicaoDeBensParaRevenda = 1,
+    AquisicaoDeBensInsumo = 2,
+    AquisicaoDeServicosInsumo = 3,
+    EnergiaEletricaETermica = 4,
+    AluguelDePredios = 5,
+    AluguelDeMaquinasEEquipamentos = 6,
+    ArmazenagemEFreteNaVenda = 7,
+    ArrendamentoMercantil = 8,
+    ImobilizadoDepreciacao = 9,
+    ImobilizadoValorDeAquisicao = 10,
+    EdificacoesEBenfeitorias = 11,
+    DevolucaoDeVendas = 12,
+    OutrasOperacoesComDireitoACredito = 13,
+    TransporteDeCargasSubcontratacao = 14,
+    ImobiliariaCustoIncorrido = 15,
+    ImobiliariaCustoOrcado = 16,
+    ServicosDeLimpezaEConservacao = 17,
+    EstoqueDeAbertura = 18,
+}
+
+impl NaturezaBaseCalculo {
+    /// All codes, in ascending order.
+    pub const ALL: [NaturezaBaseCalculo; 18] = [
+        NaturezaBaseCalculo::AquisicaoDeBensParaRevenda,
+        NaturezaBaseCalculo::AquisicaoDeBensInsumo,
+        NaturezaBaseCalculo::AquisicaoDeServicosInsumo,
+        NaturezaBaseCalculo::EnergiaEletricaETermica,
+        NaturezaBaseCalculo::AluguelDePredios,
+        NaturezaBaseCalculo::AluguelDeMaquinasEEquipamentos,
+        NaturezaBaseCalculo::ArmazenagemEFreteNaVenda,
+        NaturezaBaseCalculo::ArrendamentoMercantil,
+        NaturezaBaseCalculo::ImobilizadoDepreciacao,
+        NaturezaBaseCalculo::ImobilizadoValorDeAquisicao,
+        NaturezaBaseCalculo::EdificacoesEBenfeitorias,
+        NaturezaBaseCalculo::DevolucaoDeVendas,
+        NaturezaBaseCalculo::OutrasOperacoesComDireitoACredito,
+        NaturezaBaseCalculo::TransporteDeCargasSubcontratacao,
+        NaturezaBaseCalculo::ImobiliariaCustoIncorrido,
+        NaturezaBaseCalculo::ImobiliariaCustoOrcado,
+        NaturezaBaseCalculo::ServicosDeLimpezaEConservacao,
+        NaturezaBaseCalculo::EstoqueDeAbertura,
+    ];
+
+    /// Iterate over all codes, in ascending order.
+    pub fn iter() -> impl Iterator<Item = NaturezaBaseCalculo> {
+        NaturezaBaseCalculo::ALL.into_iter()
+    }
+
+    /// Same as `NaturezaBaseCalculo::try_from`, usable in constant expressions.
+    pub const fn from_code(code: u16) -> Option<Self> {
+        match code {
+            1..=18 => Some(NaturezaBaseCalculo::ALL[code as usize - 1]),
+            _ => None,
+        }
+    }
+
+    /// The numeric code.
+    pub const fn code(self) -> u16 {
+        self as u16
+    }
+
+    /// Acquisition of goods for resale, of goods and of services used as inputs (01 to 03).
+    pub const fn is_aquisicao(self) -> bool {
+        matches!(self.code(), 1..=3)
+    }
+
+    /// Credit on depreciation or amortization charges (09 and 11).
+    pub const fn is_depreciacao(self) -> bool {
+        matches!(self.code(), 9 | 11)
+    }
+
+    /// Fixed assets: depreciation, acquisition value or buildings (09 to 11).
+    pub const fn is_ativo_imobilizado(self) -> bool {
+        matches!(self.code(), 9..=11)
+    }
+
+    /// Rental of buildings, machines and equipment (05 and 06).
+    pub const fn is_aluguel(self) -> bool {
+        matches!(self.code(), 5 | 6)
+    }
+
+    /// Official description.
+    pub const fn descricao(self) -> &'static str {
+        match self {
+            NaturezaBaseCalculo::AquisicaoDeBensParaRevenda => "Aquisição de bens para revenda",
+            NaturezaBaseCalculo::AquisicaoDeBensInsumo => {
+                "Aquisição de bens utilizados como insumo"
+            }
+            NaturezaBaseCalculo::AquisicaoDeServicosInsumo => {
+                "Aquisição de serviços utilizados como insumo"
+            }
+            NaturezaBaseCalculo::EnergiaEletricaETermica => {
+                "Energia elétrica e térmica, inclusive sob a forma de vapor"
+            }
+            NaturezaBaseCalculo::AluguelDePredios => "Aluguéis de prédios",
+            NaturezaBaseCalculo::AluguelDeMaquinasEEquipamentos => {
+                "Aluguéis de máquinas e equipamentos"
+            }
+            NaturezaBaseCalculo::ArmazenagemEFreteNaVenda => {
+                "Armazenagem de mercadoria e frete na operação de venda"
+            }
+            NaturezaBaseCalculo::ArrendamentoMercantil => {
+                "Contraprestações de arrendamento mercantil"
+            }
+            NaturezaBaseCalculo::ImobilizadoDepreciacao => {
+                "Máquinas, equipamentos e outros bens incorporados ao ativo imobilizado (crédito sobre encargos de depreciação)"
+            }
+            NaturezaBaseCalculo::ImobilizadoValorDeAquisicao => {
+                "Máquinas, equipamentos e outros bens incorporados ao ativo imobilizado (crédito com base no valor de aquisição)"
+            }
+            NaturezaBaseCalculo::EdificacoesEBenfeitorias => {
+                "Amortização e Depreciação de edificações e benfeitorias em imóveis"
+            }
+            NaturezaBaseCalculo::DevolucaoDeVendas => {
+                "Devolução de Vendas Sujeitas à Incidência Não-Cumulativa"
+            }
+            NaturezaBaseCalculo::OutrasOperacoesComDireitoACredito => {
+                "Outras Operações com Direito a Crédito"
+            }
+            NaturezaBaseCalculo::TransporteDeCargasSubcontratacao => {
+                "Atividade de Transporte de Cargas - Subcontratação"
+            }
+            NaturezaBaseCalculo::ImobiliariaCustoIncorrido => {
+                "Atividade Imobiliária - Custo Incorrido de Unidade Imobiliária"
+            }
+            NaturezaBaseCalculo::ImobiliariaCustoOrcado => {
+                "Atividade Imobiliária - Custo Orçado de unidade não concluída"
+            }
+            NaturezaBaseCalculo::ServicosDeLimpezaEConservacao => {
+                "Atividade de Prestação de Serviços de Limpeza, Conservação e Manutenção - vale-transporte, vale-refeição ou vale-alimentação, fardamento ou uniforme"
+            }
+            NaturezaBaseCalculo::EstoqueDeAbertura => "Estoque de abertura de bens",
+        }
+    }
+}
+
+impl TryFrom<u16> for NaturezaBaseCalculo {
+    type Error = NaturezaBcError;
+
+    fn try_from(code: u16) -> Result<Self, Self::Error> {
+        NaturezaBaseCalculo::from_code(code).ok_or(NaturezaBcError::InvalidCode(code))
+    }
+}
+
+impl FromStr for NaturezaBaseCalculo {
+    type Err = NaturezaBcError;
+
+    /// Parse the SPED field: "01" or "1".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim();
+
+        if trimmed.is_empty() || trimmed.len() > 2 || !trimmed.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(NaturezaBcError::InvalidFormat(s.to_string()));
+        }
+
+        let code: u16 = trimmed
+            .parse()
+            .map_err(|_| NaturezaBcError::InvalidFormat(s.to_string()))?;
+
+        NaturezaBaseCalculo::try_from(code)
+    }
+}
+
+impl fmt::Display for NaturezaBaseCalculo {
+    /// Format with 2 digits, as in the SPED field: "01".
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}", self.code())
+    }
+}
+
+#[cfg(test)]
+mod natureza_bc_tests {
+    use super::*;
+    use crate::CODIGO_DA_NATUREZA_BC;
+
+    // cargo test -- --show-output natureza_bc_tests
+
+    #[test]
+    fn all_variants_round_trip() {
+        for nat in NaturezaBaseCalculo::iter() {
+            assert_eq!(NaturezaBaseCalculo::try_from(nat.code()), Ok(nat));
+            assert_eq!(nat.to_string().parse::<NaturezaBaseCalculo>(), Ok(nat));
+        }
+
+        let codes: Vec<u16> = NaturezaBaseCalculo::iter().map(|nat| nat.code()).collect();
+        assert_eq!(codes, CODIGO_DA_NATUREZA_BC);
+    }
+
+    #[test]
+    fn invalid_natureza() {
+        assert_eq!(
+            NaturezaBaseCalculo::try_from(0),
+            Err(NaturezaBcError::InvalidCode(0))
+        );
+        assert_eq!(
+            "19".parse::<NaturezaBaseCalculo>(),
+            Err(NaturezaBcError::InvalidCode(19))
+        );
+        assert_eq!(
+            "".parse::<NaturezaBaseCalculo>(),
+            Err(NaturezaBcError::InvalidFormat("".to_string()))
+        );
+    }
+
+    #[test]
+    fn grouping() {
+        let aquisicao: Vec<u16> = NaturezaBaseCalculo::iter()
+            .filter(|nat| nat.is_aquisicao())
+            .map(NaturezaBaseCalculo::code)
+            .collect();
+        let aluguel: Vec<u16> = NaturezaBaseCalculo::iter()
+            .filter(|nat| nat.is_aluguel())
+            .map(NaturezaBaseCalculo::code)
+            .collect();
+
+        assert_eq!(aquisicao, [1, 2, 3]);
+        assert_eq!(aluguel, [5, 6]);
+    }
+}
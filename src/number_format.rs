@@ -0,0 +1,301 @@
+use crate::{separator::split_and_insert, Decimal};
+
+/**
+Number formatter with configurable separators, sign and currency.
+
+Presets:
+- [`NumberFormat::pt_br`] formats "-1.234.567,89"
+- [`NumberFormat::en_us`] formats "-1,234,567.89"
+- [`NumberFormat::plain`] formats "-1234567.89"
+
+Example:
+```
+    use claudiofsr_lib::NumberFormat;
+
+    let format = NumberFormat::pt_br();
+    assert_eq!(format.format(-2987954368.369177), "-2.987.954.368,37");
+    assert_eq!(format.format(1234_u64), "1.234,00");
+
+    let format = NumberFormat::en_us().currency("$").parentheses(true).width(14);
+    assert_eq!(format.format(-1234.5), "   ($1,234.50)");
+    assert_eq!(format.format(i128::MAX).len(), 55);
+
+    let format = NumberFormat::pt_br().currency("R$ ").decimals(0).plus_sign(true);
+    assert_eq!(format.format(1234567), "+R$ 1.234.567");
+
+    let format = NumberFormat::plain().decimals(3);
+    assert_eq!(format.format(-0.15), "-0.150");
+```
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NumberFormat {
+    decimal_separator: char,
+    thousands_separator: Option<char>,
+    group_size: usize,
+    decimals: usize,
+    currency: String,
+    plus_sign: bool,
+    parentheses: bool,
+    width: usize,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        NumberFormat::pt_br()
+    }
+}
+
+impl NumberFormat {
+    /// Brazilian format with 2 decimal places: "1.234.567,89".
+    pub fn pt_br() -> Self {
+        NumberFormat {
+            decimal_separator: ',',
+            thousands_separator: Some('.'),
+            group_size: 3,
+            decimals: 2,
+            currency: String::new(),
+            plus_sign: false,
+            parentheses: false,
+            width: 0,
+        }
+    }
+
+    /// American format with 2 decimal places: "1,234,567.89".
+    pub fn en_us() -> Self {
+        NumberFormat {
+            decimal_separator: '.',
+            thousands_separator: Some(','),
+            ..NumberFormat::pt_br()
+        }
+    }
+
+    /// No thousands separator, dot decimals and 2 decimal places: "1234567.89".
+    pub fn plain() -> Self {
+        NumberFormat {
+            decimal_separator: '.',
+            thousands_separator: None,
+            ..NumberFormat::pt_br()
+        }
+    }
+
+    pub fn decimal_separator(mut self, separator: char) -> Self {
+        self.decimal_separator = separator;
+        self
+    }
+
+    /// Thousands separator, or `None` to not group the digits.
+    pub fn thousands_separator(mut self, separator: Option<char>) -> Self {
+        self.thousands_separator = separator;
+        self
+    }
+
+    /// Number of digits in each group (3 by default).
+    pub fn group_size(mut self, size: usize) -> Self {
+        self.group_size = size.max(1);
+        self
+    }
+
+    /// Number of decimal places.
+    pub fn decimals(mut self, decimals: usize) -> Self {
+        self.decimals = decimals;
+        self
+    }
+
+    /// Currency symbol before the digits, like "R$ " or "$".
+    pub fn currency(mut self, symbol: &str) -> Self {
+        self.currency = symbol.to_string();
+        self
+    }
+
+    /// Add "+" before positive numbers.
+    pub fn plus_sign(mut self, plus_sign: bool) -> Self {
+        self.plus_sign = plus_sign;
+        self
+    }
+
+    /// Accounting style: negative numbers in parentheses instead of "-".
+    pub fn parentheses(mut self, parentheses: bool) -> Self {
+        self.parentheses = parentheses;
+        self
+    }
+
+    /// Minimum width, padded with spaces on the left.
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Format an integer, a float or a [`Decimal`].
+    pub fn format<N: FormatNumber>(&self, number: N) -> String {
+        let formatted = match number.digits(self.decimals) {
+            Some((negative, integer, fraction)) => {
+                // Values rounded to zero are formatted without the sign.
+                let negative =
+                    negative && integer.bytes().chain(fraction.bytes()).any(|b| b != b'0');
+                let mut body = self.currency.clone();
+
+                match self.thousands_separator {
+                    Some(separator) => {
+                        body += &split_and_insert(&integer, separator, self.group_size)
+                    }
+                    None => body += &integer,
+                }
+
+                if !fraction.is_empty() {
+                    body.push(self.decimal_separator);
+                    body += &fraction;
+                }
+
+                match (negative, self.parentheses, self.plus_sign) {
+                    (true, true, _) => format!("({body})"),
+                    (true, false, _) => format!("-{body}"),
+                    (false, _, true) => format!("+{body}"),
+                    (false, _, false) => body,
+                }
+            }
+            // NaN and infinity.
+            None => number.to_string(),
+        };
+
+        format!("{formatted:>width$}", width = self.width)
+    }
+}
+
+/// Numbers accepted by [`NumberFormat::format`].
+pub trait FormatNumber: std::fmt::Display {
+    /// Sign, integer digits and `decimals` fractional digits, or `None` if not finite.
+    fn digits(&self, decimals: usize) -> Option<(bool, String, String)>;
+}
+
+macro_rules! impl_format_integer {
+    ( $($t:ty),* ) => {
+        $(
+            impl FormatNumber for $t {
+                fn digits(&self, decimals: usize) -> Option<(bool, String, String)> {
+                    Some((*self < 0, self.unsigned_abs().to_string(), "0".repeat(decimals)))
+                }
+            }
+        )*
+    };
+}
+
+impl_format_integer!(i8, i16, i32, i64, i128, isize);
+
+macro_rules! impl_format_unsigned {
+    ( $($t:ty),* ) => {
+        $(
+            impl FormatNumber for $t {
+                fn digits(&self, decimals: usize) -> Option<(bool, String, String)> {
+                    Some((false, self.to_string(), "0".repeat(decimals)))
+                }
+            }
+        )*
+    };
+}
+
+impl_format_unsigned!(u8, u16, u32, u64, u128, usize);
+
+macro_rules! impl_format_float {
+    ( $($t:ty),* ) => {
+        $(
+            impl FormatNumber for $t {
+                fn digits(&self, decimals: usize) -> Option<(bool, String, String)> {
+                    if !self.is_finite() {
+                        return None;
+                    }
+
+                    let round: String = format!("{:0.decimals$}", self.abs());
+                    let (integer, fraction) = round.split_once('.').unwrap_or((&round, ""));
+
+                    Some((self.is_sign_negative(), integer.to_string(), fraction.to_string()))
+                }
+            }
+        )*
+    };
+}
+
+impl_format_float!(f32, f64);
+
+impl FormatNumber for Decimal {
+    fn digits(&self, decimals: usize) -> Option<(bool, String, String)> {
+        let places = u32::try_from(decimals).ok()?;
+        let rounded = self.round(places);
+
+        // The scale is smaller than `decimals` only if the mantissa does not fit.
+        let decimals = rounded.scale() as usize;
+        let digits = rounded.mantissa().unsigned_abs().to_string();
+        let digits = format!("{digits:0>width$}", width = decimals + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - decimals);
+
+        Some((
+            rounded.is_negative(),
+            integer.to_string(),
+            fraction.to_string(),
+        ))
+    }
+}
+
+impl<N: FormatNumber> FormatNumber for &N {
+    fn digits(&self, decimals: usize) -> Option<(bool, String, String)> {
+        (*self).digits(decimals)
+    }
+}
+
+#[cfg(test)]
+mod number_format_tests {
+    use super::*;
+
+    // cargo test -- --show-output number_format_tests
+
+    #[test]
+    fn presets() {
+        let value = -1234567.891;
+
+        assert_eq!(NumberFormat::pt_br().format(value), "-1.234.567,89");
+        assert_eq!(NumberFormat::en_us().format(value), "-1,234,567.89");
+        assert_eq!(NumberFormat::plain().format(value), "-1234567.89");
+        assert_eq!(NumberFormat::default().decimals(0).format(999.4), "999");
+        assert_eq!(NumberFormat::pt_br().format(f64::NAN), "NaN");
+    }
+
+    #[test]
+    fn integers_and_decimals() {
+        let format = NumberFormat::pt_br().decimals(0);
+
+        assert_eq!(format.format(u64::MAX), "18.446.744.073.709.551.615");
+        assert_eq!(
+            format.format(i128::MIN),
+            "-170.141.183.460.469.231.731.687.303.715.884.105.728"
+        );
+        let values: Vec<String> = [-5_i8, 7]
+            .iter()
+            .map(|value| format.format(value))
+            .collect();
+        assert_eq!(values, ["-5", "7"]);
+
+        let value = Decimal::new(-123456789, 4);
+        assert_eq!(NumberFormat::pt_br().format(value), "-12.345,68");
+        assert_eq!(
+            NumberFormat::en_us().decimals(5).format(value),
+            "-12,345.67890"
+        );
+    }
+
+    #[test]
+    fn options() {
+        let format = NumberFormat::pt_br()
+            .currency("R$ ")
+            .parentheses(true)
+            .plus_sign(true)
+            .width(16);
+
+        assert_eq!(format.format(-1234.5), "   (R$ 1.234,50)");
+        assert_eq!(format.format(1234.5), "    +R$ 1.234,50");
+
+        let format = NumberFormat::plain()
+            .thousands_separator(Some(' '))
+            .group_size(4)
+            .decimal_separator(',');
+        assert_eq!(format.format(123456789.0), "1 2345 6789,00");
+    }
+}
@@ -0,0 +1,369 @@
+use crate::Decimal;
+use std::fmt;
+
+/// Errors returned when parsing a formatted number.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NumberError {
+    /// No digits were found.
+    Empty,
+    /// A character that is not a digit, separator, sign or currency symbol.
+    InvalidCharacter(char),
+    /// More than one sign, or unbalanced parentheses.
+    InvalidSign(String),
+    /// Separators in the wrong place, like "1.23.4" or "1,2,3".
+    MisplacedSeparator(String),
+    /// A single separator that can be decimal or thousands, like "1.234".
+    Ambiguous(String),
+    /// The number has fractional digits and the target is an integer.
+    NotAnInteger(String),
+    /// The number does not fit the target type.
+    OutOfRange(String),
+}
+
+impl fmt::Display for NumberError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NumberError::Empty => write!(f, "number without digits"),
+            NumberError::InvalidCharacter(ch) => write!(f, "invalid character in number: {ch:?}"),
+            NumberError::InvalidSign(s) => write!(f, "invalid sign in number: {s:?}"),
+            NumberError::MisplacedSeparator(s) => {
+                write!(f, "misplaced separator in number: {s:?}")
+            }
+            NumberError::Ambiguous(s) => {
+                write!(f, "ambiguous separator, decimal or thousands: {s:?}")
+            }
+            NumberError::NotAnInteger(s) => write!(f, "number is not an integer: {s:?}"),
+            NumberError::OutOfRange(s) => write!(f, "number out of range: {s:?}"),
+        }
+    }
+}
+
+impl std::error::Error for NumberError {}
+
+/// Which separator is the decimal one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum NumberLocale {
+    /// Brazilian format: "1.234.567,89".
+    PtBr,
+    /// American format: "1,234,567.89".
+    EnUs,
+    /// Detect from the text, returning [`NumberError::Ambiguous`] when it is not possible.
+    #[default]
+    Auto,
+}
+
+/**
+Number parsed from text: the exact [`Decimal`].
+
+Accepted formats:
+- thousands separators, optional: "1.234.567,89" or "1234567,89";
+- comma or dot decimals: "1234,5" or "1234.5";
+- leading sign, trailing minus or parentheses: "-10", "10-" or "(10)";
+- "R$" prefix: "R$ 1.234,56" or "-R$ 1,00".
+
+With [`NumberLocale::Auto`], a single separator followed by exactly three
+digits, like "1.234", is ambiguous.
+
+Example:
+```
+    use claudiofsr_lib::{parse_br_number, BrNumber, NumberError, NumberLocale};
+
+    let number: BrNumber = "R$ -1.234.567,89".parse().unwrap();
+    assert_eq!(number.mantissa(), -123456789);
+    assert_eq!(number.scale(), 2);
+
+    assert_eq!(parse_br_number::<f64>("(1.234,5)"), Ok(-1234.5));
+    assert_eq!(parse_br_number::<f64>("1,234,567.89"), Ok(1234567.89));
+    assert_eq!(parse_br_number::<i64>("2.500.000-"), Ok(-2500000));
+    assert_eq!(parse_br_number::<u8>("256"), Err(NumberError::OutOfRange("256".to_string())));
+
+    assert_eq!(
+        parse_br_number::<f64>("1.234"),
+        Err(NumberError::Ambiguous("1.234".to_string()))
+    );
+    let number = BrNumber::parse_with("1.234", NumberLocale::PtBr).unwrap();
+    assert_eq!(number.to_f64(), 1234.0);
+```
+*/
+pub type BrNumber = Decimal;
+
+/// Parse with the decimal separator of the locale, see [`BrNumber`].
+pub(crate) fn parse_number(text: &str, locale: NumberLocale) -> Result<Decimal, NumberError> {
+    let (negative, body) = strip_sign(text)?;
+
+    if body.is_empty() {
+        return Err(NumberError::Empty);
+    }
+
+    if let Some(ch) = body.chars().find(|c| !matches!(c, '0'..='9' | '.' | ',')) {
+        return Err(NumberError::InvalidCharacter(ch));
+    }
+
+    let misplaced = || NumberError::MisplacedSeparator(text.to_string());
+
+    if !body.starts_with(|c: char| c.is_ascii_digit())
+        || !body.ends_with(|c: char| c.is_ascii_digit())
+    {
+        return Err(misplaced());
+    }
+
+    let decimal = match locale {
+        NumberLocale::PtBr => Some(','),
+        NumberLocale::EnUs => Some('.'),
+        NumberLocale::Auto => detect_decimal(body, text)?,
+    };
+
+    let thousands = match decimal {
+        Some(',') => '.',
+        Some(_) => ',',
+        None if body.contains(',') => ',',
+        None => '.',
+    };
+
+    let (integer, fraction) = match decimal.and_then(|sep| body.rsplit_once(sep)) {
+        Some((integer, fraction)) => (integer, fraction),
+        None => (body, ""),
+    };
+
+    if !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(misplaced());
+    }
+
+    let integer = remove_thousands(integer, thousands).ok_or_else(misplaced)?;
+
+    let out_of_range = || NumberError::OutOfRange(text.to_string());
+    let mut mantissa: i128 = 0;
+
+    for byte in integer.bytes().chain(fraction.bytes()) {
+        mantissa = mantissa
+            .checked_mul(10)
+            .and_then(|m| m.checked_add(i128::from(byte - b'0')))
+            .ok_or_else(out_of_range)?;
+    }
+
+    let scale = u32::try_from(fraction.len()).map_err(|_| out_of_range())?;
+
+    Ok(Decimal::new(
+        if negative { -mantissa } else { mantissa },
+        scale,
+    ))
+}
+
+/// Types that can be built from a parsed [`BrNumber`].
+pub trait FromBrNumber: Sized {
+    /// Convert the number; `text` is the original input, used in errors.
+    fn from_br_number(number: Decimal, text: &str) -> Result<Self, NumberError>;
+}
+
+impl FromBrNumber for Decimal {
+    fn from_br_number(number: Decimal, _text: &str) -> Result<Self, NumberError> {
+        Ok(number)
+    }
+}
+
+impl FromBrNumber for f64 {
+    fn from_br_number(number: Decimal, _text: &str) -> Result<Self, NumberError> {
+        Ok(number.to_f64())
+    }
+}
+
+impl FromBrNumber for f32 {
+    fn from_br_number(number: Decimal, _text: &str) -> Result<Self, NumberError> {
+        Ok(number.to_f32())
+    }
+}
+
+macro_rules! impl_from_br_number {
+    ( $($t:ty),* ) => {
+        $(
+            impl FromBrNumber for $t {
+                fn from_br_number(number: Decimal, text: &str) -> Result<Self, NumberError> {
+                    let integer = number
+                        .integer_part()
+                        .ok_or_else(|| NumberError::NotAnInteger(text.to_string()))?;
+                    <$t>::try_from(integer).map_err(|_| NumberError::OutOfRange(text.to_string()))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_br_number!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// Parse a formatted number, detecting the decimal separator.
+///
+/// See [`BrNumber`] for the accepted formats.
+pub fn parse_br_number<T: FromBrNumber>(text: &str) -> Result<T, NumberError> {
+    T::from_br_number(Decimal::parse(text)?, text)
+}
+
+/// Remove spaces, "R$", sign and parentheses, returning `(negative, digits)`.
+fn strip_sign(text: &str) -> Result<(bool, &str), NumberError> {
+    let invalid = || NumberError::InvalidSign(text.to_string());
+    let mut body = text.trim();
+    let mut signs = 0;
+    let mut negative = false;
+
+    if let Some(inner) = body.strip_prefix('(') {
+        body = inner.strip_suffix(')').ok_or_else(invalid)?.trim();
+        signs += 1;
+        negative = true;
+    }
+
+    for _ in 0..2 {
+        if let Some(rest) = body.strip_prefix(['-', '+']) {
+            negative |= body.starts_with('-');
+            signs += 1;
+            body = rest.trim();
+        }
+        if let Some(rest) = body.strip_prefix("R$") {
+            body = rest.trim();
+        }
+    }
+
+    if let Some(rest) = body.strip_suffix('-') {
+        negative = true;
+        signs += 1;
+        body = rest.trim();
+    }
+
+    if signs > 1 || body.contains(['(', ')', '-', '+']) {
+        return Err(invalid());
+    }
+
+    Ok((negative, body))
+}
+
+/// Find the decimal separator of a number with digits, '.' and ','.
+fn detect_decimal(body: &str, text: &str) -> Result<Option<char>, NumberError> {
+    let dots = body.matches('.').count();
+    let commas = body.matches(',').count();
+
+    let single = match (dots, commas) {
+        (0, 0) => return Ok(None),
+        // Both: the last one is the decimal separator.
+        (_, _) if dots > 0 && commas > 0 => {
+            let last = body
+                .rfind(['.', ','])
+                .and_then(|i| body[i..].chars().next());
+            return Ok(last);
+        }
+        // Repeated: thousands separator.
+        (1, 0) => '.',
+        (0, 1) => ',',
+        _ => return Ok(None),
+    };
+
+    let (integer, fraction) = body.split_once(single).unwrap_or((body, ""));
+
+    // "0.123" and "1234.567" can not have a thousands separator.
+    if fraction.len() != 3 || integer.len() > 3 || integer.starts_with('0') {
+        return Ok(Some(single));
+    }
+
+    Err(NumberError::Ambiguous(text.to_string()))
+}
+
+/// Remove valid thousands separators: groups of 3 digits after the first one.
+fn remove_thousands(integer: &str, thousands: char) -> Option<String> {
+    let groups: Vec<&str> = integer.split(thousands).collect();
+
+    if groups.len() > 1 {
+        let first = groups[0].len();
+        let valid = (1..=3).contains(&first) && groups[1..].iter().all(|g| g.len() == 3);
+        if !valid {
+            return None;
+        }
+    }
+
+    if groups
+        .iter()
+        .any(|g| !g.bytes().all(|b| b.is_ascii_digit()))
+    {
+        return None;
+    }
+
+    Some(groups.concat())
+}
+
+#[cfg(test)]
+mod number_parser_tests {
+    use super::*;
+
+    // cargo test -- --show-output number_parser_tests
+
+    #[test]
+    fn parse_formats() {
+        let cases = [
+            ("1.234.567,89", 123456789, 2),
+            ("1234567,89", 123456789, 2),
+            ("1,234,567.89", 123456789, 2),
+            ("1.234.567", 1234567, 0),
+            ("0,5", 5, 1),
+            ("0.123", 123, 3),
+            ("1234.567", 1234567, 3),
+            ("-R$ 10,00", -1000, 2),
+            ("R$ -10,00", -1000, 2),
+            ("( R$ 10,00 )", -1000, 2),
+            ("10,00-", -1000, 2),
+            ("+7", 7, 0),
+        ];
+
+        for (text, mantissa, scale) in cases {
+            assert_eq!(
+                BrNumber::parse(text),
+                Ok(BrNumber::new(mantissa, scale)),
+                "{text}"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = |text: &str| BrNumber::parse(text).unwrap_err();
+
+        assert_eq!(error(""), NumberError::Empty);
+        assert_eq!(error("R$"), NumberError::Empty);
+        assert_eq!(error("12a"), NumberError::InvalidCharacter('a'));
+        assert!(matches!(error("-10-"), NumberError::InvalidSign(_)));
+        assert!(matches!(error("(10"), NumberError::InvalidSign(_)));
+        assert!(matches!(
+            error("1.23.4"),
+            NumberError::MisplacedSeparator(_)
+        ));
+        assert!(matches!(error("1,2,3"), NumberError::MisplacedSeparator(_)));
+        assert!(matches!(
+            error("1.234,5.6"),
+            NumberError::MisplacedSeparator(_)
+        ));
+        assert!(matches!(error(",5"), NumberError::MisplacedSeparator(_)));
+        assert!(matches!(
+            error("1,234.567,8"),
+            NumberError::MisplacedSeparator(_)
+        ));
+        assert!(matches!(error("123,456"), NumberError::Ambiguous(_)));
+        assert!(matches!(
+            BrNumber::parse_with("1234.56", NumberLocale::PtBr),
+            Err(NumberError::MisplacedSeparator(_))
+        ));
+    }
+
+    #[test]
+    fn parse_targets() {
+        assert_eq!(parse_br_number::<f64>("1.234,56"), Ok(1234.56));
+        assert_eq!(parse_br_number::<f32>("0,1"), Ok(0.1));
+        assert_eq!(parse_br_number::<i32>("1.234,00"), Ok(1234));
+        assert_eq!(
+            parse_br_number::<i32>("1.234,50"),
+            Err(NumberError::NotAnInteger("1.234,50".to_string()))
+        );
+        assert_eq!(
+            parse_br_number::<u32>("-1"),
+            Err(NumberError::OutOfRange("-1".to_string()))
+        );
+        assert!(matches!(
+            parse_br_number::<i128>(&"9".repeat(40)),
+            Err(NumberError::OutOfRange(_))
+        ));
+    }
+}
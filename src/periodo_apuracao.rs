@@ -0,0 +1,357 @@
+use chrono::{Datelike, NaiveDate};
+use std::{
+    fmt,
+    ops::{Add, Sub},
+    str::FromStr,
+};
+
+/// Errors returned when creating or parsing a tax period.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PeriodoError {
+    /// Not "MMYYYY", "YYYY-MM" or "MM/YYYY".
+    InvalidFormat(String),
+    /// Month outside 1 to 12.
+    InvalidMonth(u32),
+    /// Year outside the range of `chrono::NaiveDate`.
+    InvalidYear(i32),
+}
+
+impl fmt::Display for PeriodoError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PeriodoError::InvalidFormat(text) => {
+                write!(
+                    f,
+                    "invalid period '{text}': expected MMYYYY, YYYY-MM or MM/YYYY"
+                )
+            }
+            PeriodoError::InvalidMonth(month) => write!(f, "invalid month: {month}"),
+            PeriodoError::InvalidYear(year) => write!(f, "year out of range: {year}"),
+        }
+    }
+}
+
+impl std::error::Error for PeriodoError {}
+
+/**
+Monthly tax period (período de apuração).
+
+Parsed from "MMYYYY" (SPED), "YYYY-MM" or "MM/YYYY" and displayed as "MM/YYYY".
+
+Ordered by year and month, so it can key a `BTreeMap`.
+
+Example:
+```
+    use claudiofsr_lib::PeriodoApuracao;
+    use chrono::NaiveDate;
+
+    let periodo: PeriodoApuracao = "022024".parse().unwrap();
+
+    assert_eq!(periodo, "2024-02".parse().unwrap());
+    assert_eq!(periodo, "2/2024".parse().unwrap());
+    assert_eq!(periodo.to_string(), "02/2024");
+    assert_eq!(periodo.to_mmyyyy(), "022024");
+    assert_eq!(periodo.last_day(), NaiveDate::from_ymd_opt(2024, 2, 29).unwrap());
+    assert_eq!(periodo.quarter(), 1);
+    assert_eq!((periodo + 11).to_string(), "01/2025");
+
+    let months: Vec<String> = periodo
+        .range_to(periodo + 2)
+        .map(|p| p.to_string())
+        .collect();
+    assert_eq!(months, ["02/2024", "03/2024", "04/2024"]);
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PeriodoApuracao {
+    year: i32,
+    month: u32,
+}
+
+impl PeriodoApuracao {
+    /// Period of the year and month (1 to 12).
+    pub fn new(year: i32, month: u32) -> Result<Self, PeriodoError> {
+        if !(1..=12).contains(&month) {
+            return Err(PeriodoError::InvalidMonth(month));
+        }
+
+        match NaiveDate::from_ymd_opt(year, month, 1) {
+            Some(_) => Ok(PeriodoApuracao { year, month }),
+            None => Err(PeriodoError::InvalidYear(year)),
+        }
+    }
+
+    /// Year of the period.
+    pub const fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// Month of the period, from 1 to 12.
+    pub const fn month(&self) -> u32 {
+        self.month
+    }
+
+    /// First day of the month.
+    pub fn first_day(&self) -> NaiveDate {
+        NaiveDate::from_ymd_opt(self.year, self.month, 1).expect("valid by construction")
+    }
+
+    /// Last day of the month, February 29 in leap years.
+    pub fn last_day(&self) -> NaiveDate {
+        (28..=31)
+            .rev()
+            .find_map(|day| NaiveDate::from_ymd_opt(self.year, self.month, day))
+            .expect("valid by construction")
+    }
+
+    /// The date is in the month of the period.
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        PeriodoApuracao::from(date) == *self
+    }
+
+    /// Move forward or backward `months` months.
+    ///
+    /// Returns `None` outside the range of `chrono::NaiveDate`.
+    pub fn checked_add_months(&self, months: i32) -> Option<Self> {
+        let index = self.index() + i64::from(months);
+        let year = i32::try_from(index.div_euclid(12)).ok()?;
+        let month = index.rem_euclid(12) as u32 + 1;
+        PeriodoApuracao::new(year, month).ok()
+    }
+
+    /// The following month, `None` outside the range of `chrono::NaiveDate`.
+    pub fn next(&self) -> Option<Self> {
+        self.checked_add_months(1)
+    }
+
+    /// The previous month, `None` outside the range of `chrono::NaiveDate`.
+    pub fn previous(&self) -> Option<Self> {
+        self.checked_add_months(-1)
+    }
+
+    /// Quarter of the year, from 1 to 4.
+    pub const fn quarter(&self) -> u32 {
+        (self.month - 1) / 3 + 1
+    }
+
+    /// Semester of the year, 1 or 2.
+    pub const fn semester(&self) -> u32 {
+        (self.month - 1) / 6 + 1
+    }
+
+    /// The three months of the quarter.
+    pub fn quarter_range(&self) -> PeriodoRange {
+        let first = PeriodoApuracao {
+            year: self.year,
+            month: (self.quarter() - 1) * 3 + 1,
+        };
+        first.range_to(PeriodoApuracao {
+            month: first.month + 2,
+            ..first
+        })
+    }
+
+    /// The six months of the semester.
+    pub fn semester_range(&self) -> PeriodoRange {
+        let first = PeriodoApuracao {
+            year: self.year,
+            month: (self.semester() - 1) * 6 + 1,
+        };
+        first.range_to(PeriodoApuracao {
+            month: first.month + 5,
+            ..first
+        })
+    }
+
+    /// Iterate month by month from `self` to `end`, inclusive.
+    pub fn range_to(self, end: Self) -> PeriodoRange {
+        PeriodoRange {
+            next: Some(self).filter(|start| *start <= end),
+            end,
+        }
+    }
+
+    /// SPED format: "MMYYYY".
+    pub fn to_mmyyyy(&self) -> String {
+        format!("{:02}{:04}", self.month, self.year)
+    }
+
+    /// ISO 8601 format: "YYYY-MM".
+    pub fn to_iso(&self) -> String {
+        format!("{:04}-{:02}", self.year, self.month)
+    }
+
+    /// Months since year zero.
+    fn index(&self) -> i64 {
+        i64::from(self.year) * 12 + i64::from(self.month) - 1
+    }
+}
+
+impl From<NaiveDate> for PeriodoApuracao {
+    fn from(date: NaiveDate) -> Self {
+        PeriodoApuracao {
+            year: date.year(),
+            month: date.month(),
+        }
+    }
+}
+
+impl FromStr for PeriodoApuracao {
+    type Err = PeriodoError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim();
+        let invalid = || PeriodoError::InvalidFormat(text.to_string());
+        let is_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+
+        let (year, month) = match (text.split_once('-'), text.split_once('/')) {
+            (Some((year, month)), None) if year.len() == 4 && month.len() == 2 => (year, month),
+            (None, Some((month, year))) if (1..=2).contains(&month.len()) && year.len() == 4 => {
+                (year, month)
+            }
+            (None, None) if text.len() == 6 && is_digits(text) => (&text[2..], &text[..2]),
+            _ => return Err(invalid()),
+        };
+
+        if !is_digits(year) || !is_digits(month) {
+            return Err(invalid());
+        }
+
+        let year: i32 = year.parse().map_err(|_| invalid())?;
+        let month: u32 = month.parse().map_err(|_| invalid())?;
+
+        PeriodoApuracao::new(year, month)
+    }
+}
+
+impl fmt::Display for PeriodoApuracao {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:02}/{:04}", self.month, self.year)
+    }
+}
+
+impl Add<i32> for PeriodoApuracao {
+    type Output = Self;
+
+    /// Panics outside the range of `chrono::NaiveDate`.
+    fn add(self, months: i32) -> Self {
+        self.checked_add_months(months)
+            .expect("PeriodoApuracao out of range")
+    }
+}
+
+impl Sub<i32> for PeriodoApuracao {
+    type Output = Self;
+
+    /// Panics outside the range of `chrono::NaiveDate`.
+    fn sub(self, months: i32) -> Self {
+        months
+            .checked_neg()
+            .and_then(|months| self.checked_add_months(months))
+            .expect("PeriodoApuracao out of range")
+    }
+}
+
+impl Sub for PeriodoApuracao {
+    type Output = i64;
+
+    /// Number of months between the periods.
+    fn sub(self, other: Self) -> i64 {
+        self.index() - other.index()
+    }
+}
+
+/// Inclusive range of periods, created by [`PeriodoApuracao::range_to`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeriodoRange {
+    next: Option<PeriodoApuracao>,
+    end: PeriodoApuracao,
+}
+
+impl Iterator for PeriodoRange {
+    type Item = PeriodoApuracao;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+        self.next = current.next().filter(|next| *next <= self.end);
+        Some(current)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.next.map_or(0, |next| (self.end - next + 1) as usize);
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for PeriodoRange {}
+
+#[cfg(test)]
+mod periodo_apuracao_tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    // cargo test -- --show-output periodo_apuracao_tests
+
+    fn periodo(year: i32, month: u32) -> PeriodoApuracao {
+        PeriodoApuracao::new(year, month).unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!("122023".parse(), Ok(periodo(2023, 12)));
+        assert_eq!("2023-12".parse(), Ok(periodo(2023, 12)));
+        assert_eq!(" 12/2023 ".parse(), Ok(periodo(2023, 12)));
+
+        assert_eq!(
+            "132023".parse::<PeriodoApuracao>(),
+            Err(PeriodoError::InvalidMonth(13))
+        );
+        assert_eq!(
+            "00/2023".parse::<PeriodoApuracao>(),
+            Err(PeriodoError::InvalidMonth(0))
+        );
+        for text in ["2023-1", "12-2023", "2023/12", "+12023", "12/+023", ""] {
+            let error = PeriodoError::InvalidFormat(text.to_string());
+            assert_eq!(text.parse::<PeriodoApuracao>(), Err(error), "{text}");
+        }
+    }
+
+    #[test]
+    fn days_and_arithmetic() {
+        let p = periodo(2023, 2);
+        assert_eq!(p.first_day(), NaiveDate::from_ymd_opt(2023, 2, 1).unwrap());
+        assert_eq!(p.last_day(), NaiveDate::from_ymd_opt(2023, 2, 28).unwrap());
+        assert!(p.contains(NaiveDate::from_ymd_opt(2023, 2, 15).unwrap()));
+        assert!(!p.contains(NaiveDate::from_ymd_opt(2024, 2, 15).unwrap()));
+
+        assert_eq!(p + 11, periodo(2024, 1));
+        assert_eq!(p - 2, periodo(2022, 12));
+        assert_eq!(p + -14, periodo(2021, 12));
+        assert_eq!(periodo(2024, 1) - p, 11);
+        assert_eq!(p.previous(), Some(periodo(2023, 1)));
+        assert_eq!(p.checked_add_months(i32::MAX), None);
+        assert_eq!(p.to_iso(), "2023-02");
+    }
+
+    #[test]
+    fn ranges() {
+        let p = periodo(2024, 8);
+        assert_eq!((p.quarter(), p.semester()), (3, 2));
+
+        let quarter: Vec<u32> = p.quarter_range().map(|p| p.month()).collect();
+        assert_eq!(quarter, [7, 8, 9]);
+        assert_eq!(p.semester_range().len(), 6);
+
+        let range = periodo(2023, 11).range_to(periodo(2024, 2));
+        assert_eq!(range.len(), 4);
+
+        let mut totals: BTreeMap<PeriodoApuracao, usize> = BTreeMap::new();
+        for (index, periodo) in range.collect::<Vec<_>>().into_iter().rev().enumerate() {
+            totals.insert(periodo, index);
+        }
+        let keys: Vec<String> = totals.keys().map(|p| p.to_mmyyyy()).collect();
+        assert_eq!(keys, ["112023", "122023", "012024", "022024"]);
+
+        assert_eq!(periodo(2024, 2).range_to(periodo(2024, 1)).count(), 0);
+    }
+}
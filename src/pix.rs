@@ -0,0 +1,612 @@
+use crate::{Decimal, NumberFormat, NumberLocale, StrExtension};
+use std::{fmt, str::FromStr};
+
+/// Globally unique identifier of the PIX arrangement (tag 26, subtag 00).
+const PIX_GUI: &str = "br.gov.bcb.pix";
+
+const NOME_MAX_LEN: usize = 25;
+const CIDADE_MAX_LEN: usize = 15;
+const TXID_MAX_LEN: usize = 25;
+const CHAVE_MAX_LEN: usize = 77;
+const VALOR_MAX_LEN: usize = 13;
+
+/// Maximum length of the value of a TLV field.
+const TLV_MAX_LEN: usize = 99;
+
+/// Errors returned when building or parsing a PIX payload.
+///
+/// Tags are named like "59" or, for subtags, "26.01".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PixError {
+    /// Tag that is not two digits.
+    InvalidTag(String),
+    /// Length that is not two digits.
+    InvalidLength { tag: String, length: String },
+    /// The value is shorter than its length.
+    Truncated { tag: String },
+    /// A required tag, or subtag, was not found.
+    MissingTag(String),
+    /// The value is not allowed for the tag, like a country other than "BR".
+    InvalidValue { tag: String, value: String },
+    /// The value, after normalization, exceeds the maximum length of the tag.
+    ValueTooLong { tag: String, max: usize },
+    /// The CRC16 of tag 63 does not match the one calculated over the payload.
+    InvalidCrc { expected: String, found: String },
+}
+
+impl fmt::Display for PixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PixError::InvalidTag(tag) => write!(f, "invalid PIX tag: '{tag}'"),
+            PixError::InvalidLength { tag, length } => {
+                write!(f, "invalid length of PIX tag {tag}: '{length}'")
+            }
+            PixError::Truncated { tag } => write!(f, "truncated value of PIX tag {tag}"),
+            PixError::MissingTag(tag) => write!(f, "missing PIX tag {tag}"),
+            PixError::InvalidValue { tag, value } => {
+                write!(f, "invalid value of PIX tag {tag}: '{value}'")
+            }
+            PixError::ValueTooLong { tag, max } => {
+                write!(f, "value of PIX tag {tag} longer than {max} characters")
+            }
+            PixError::InvalidCrc { expected, found } => {
+                write!(f, "invalid PIX CRC16: expected {expected}, found {found}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PixError {}
+
+/**
+Static PIX charge: the "BR Code" payload (EMV MPM) of QR codes and
+"PIX copia e cola".
+
+The merchant name and city are normalized: accents removed, only printable
+ASCII, and truncated to 25 and 15 characters.
+
+Example:
+```
+    use claudiofsr_lib::{Decimal, PixPayload};
+
+    let pix = PixPayload::new("123e4567-e12b-12d1-a456-426655440000", "Fulano de Tal", "BRASILIA")
+        .unwrap();
+
+    let payload = pix.to_string();
+    assert_eq!(
+        payload,
+        "00020126580014br.gov.bcb.pix0136123e4567-e12b-12d1-a456-426655440000\
+         5204000053039865802BR5913Fulano de Tal6008BRASILIA62070503***63041D3D"
+    );
+    assert_eq!(payload.parse::<PixPayload>().unwrap(), pix);
+
+    let pix = PixPayload::new("fulano@example.com", "José da Conceição", "São José dos Campos")
+        .unwrap()
+        .with_valor(Decimal::new(1050, 2))
+        .unwrap()
+        .with_txid("REEMBOLSO2024")
+        .unwrap();
+
+    assert_eq!(pix.nome(), "Jose da Conceicao");
+    assert_eq!(pix.cidade(), "Sao Jose dos Ca");
+    assert_eq!(pix.valor(), Some(Decimal::new(105, 1)));
+    assert!(pix.to_string().contains("540510.50"));
+```
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PixPayload {
+    chave: String,
+    descricao: Option<String>,
+    valor: Option<Decimal>,
+    nome: String,
+    cidade: String,
+    txid: String,
+}
+
+impl PixPayload {
+    /// PIX key (CPF, CNPJ, e-mail, phone or random key), merchant name and city.
+    pub fn new(chave: &str, nome: &str, cidade: &str) -> Result<Self, PixError> {
+        let chave = chave.trim();
+
+        if chave.is_empty() || chave.chars().count() > CHAVE_MAX_LEN {
+            return Err(PixError::InvalidValue {
+                tag: "26.01".to_string(),
+                value: chave.to_string(),
+            });
+        }
+
+        let nome = normalize_pix_text(nome, NOME_MAX_LEN);
+        let cidade = normalize_pix_text(cidade, CIDADE_MAX_LEN);
+
+        for (tag, value) in [("59", &nome), ("60", &cidade)] {
+            if value.is_empty() {
+                return Err(PixError::MissingTag(tag.to_string()));
+            }
+        }
+
+        Ok(PixPayload {
+            chave: chave.to_string(),
+            descricao: None,
+            valor: None,
+            nome,
+            cidade,
+            txid: "***".to_string(),
+        })
+    }
+
+    /// Amount in reais, greater than zero. Rounded to 2 decimal places.
+    pub fn with_valor(mut self, valor: Decimal) -> Result<Self, PixError> {
+        let valor = valor.round(2);
+        let text = NumberFormat::plain().format(valor);
+
+        if valor.is_negative() || valor.is_zero() {
+            return Err(PixError::InvalidValue {
+                tag: "54".to_string(),
+                value: text,
+            });
+        }
+
+        if text.len() > VALOR_MAX_LEN {
+            return Err(PixError::ValueTooLong {
+                tag: "54".to_string(),
+                max: VALOR_MAX_LEN,
+            });
+        }
+
+        self.valor = Some(valor);
+        Ok(self)
+    }
+
+    /// Transaction identifier: up to 25 letters and digits.
+    pub fn with_txid(mut self, txid: &str) -> Result<Self, PixError> {
+        if txid.is_empty() || !txid.chars().all(|ch| ch.is_ascii_alphanumeric()) {
+            return Err(PixError::InvalidValue {
+                tag: "62.05".to_string(),
+                value: txid.to_string(),
+            });
+        }
+
+        if txid.len() > TXID_MAX_LEN {
+            return Err(PixError::ValueTooLong {
+                tag: "62.05".to_string(),
+                max: TXID_MAX_LEN,
+            });
+        }
+
+        self.txid = txid.to_string();
+        Ok(self)
+    }
+
+    /// Message to the payer, limited by the size of the merchant account field.
+    pub fn with_descricao(mut self, descricao: &str) -> Result<Self, PixError> {
+        let descricao = normalize_pix_text(descricao, TLV_MAX_LEN);
+        self.descricao = Some(descricao).filter(|d| !d.is_empty());
+
+        if self.merchant_account().chars().count() > TLV_MAX_LEN {
+            return Err(PixError::ValueTooLong {
+                tag: "26".to_string(),
+                max: TLV_MAX_LEN,
+            });
+        }
+
+        Ok(self)
+    }
+
+    /// PIX key of the receiver (tag 26, subtag 01).
+    pub fn chave(&self) -> &str {
+        &self.chave
+    }
+
+    /// Message to the payer (tag 26, subtag 02).
+    pub fn descricao(&self) -> Option<&str> {
+        self.descricao.as_deref()
+    }
+
+    /// Amount in reais (tag 54), `None` if the payer chooses it.
+    pub fn valor(&self) -> Option<Decimal> {
+        self.valor
+    }
+
+    /// Normalized merchant name (tag 59).
+    pub fn nome(&self) -> &str {
+        &self.nome
+    }
+
+    /// Normalized merchant city (tag 60).
+    pub fn cidade(&self) -> &str {
+        &self.cidade
+    }
+
+    /// Transaction identifier, "***" if not given.
+    pub fn txid(&self) -> &str {
+        &self.txid
+    }
+
+    /// The payload with the CRC16.
+    pub fn to_payload(&self) -> String {
+        let mut payload = tlv("00", "01");
+        payload += &tlv("26", &self.merchant_account());
+        payload += &tlv("52", "0000");
+        payload += &tlv("53", "986");
+
+        if let Some(valor) = self.valor {
+            payload += &tlv("54", &NumberFormat::plain().format(valor));
+        }
+
+        payload += &tlv("58", "BR");
+        payload += &tlv("59", &self.nome);
+        payload += &tlv("60", &self.cidade);
+        payload += &tlv("62", &tlv("05", &self.txid));
+        payload += "6304";
+
+        let crc = crc16_ccitt(payload.as_bytes());
+        format!("{payload}{crc:04X}")
+    }
+
+    /// Parse and validate a payload, including the CRC16.
+    ///
+    /// The merchant category code (tag 52) must have 4 digits and the
+    /// optional point of initiation method (tag 01) must be "11" or "12".
+    pub fn parse(payload: &str) -> Result<Self, PixError> {
+        let payload = payload.trim();
+
+        let (data, found) = match payload.char_indices().nth_back(3) {
+            Some((index, _)) if payload[..index].ends_with("6304") => payload.split_at(index),
+            _ => return Err(PixError::MissingTag("63".to_string())),
+        };
+
+        let expected = format!("{:04X}", crc16_ccitt(data.as_bytes()));
+        if !found.eq_ignore_ascii_case(&expected) {
+            return Err(PixError::InvalidCrc {
+                expected,
+                found: found.to_string(),
+            });
+        }
+
+        let fields = parse_tlv(payload, "")?;
+        let get = |tag: &str| fields.iter().find(|(t, _)| *t == tag).map(|(_, v)| *v);
+        let require = |tag: &str| get(tag).ok_or_else(|| PixError::MissingTag(tag.to_string()));
+
+        expect_value("00", require("00")?, "01")?;
+        if let Some(method) = get("01").filter(|method| !matches!(*method, "11" | "12")) {
+            return Err(PixError::InvalidValue {
+                tag: "01".to_string(),
+                value: method.to_string(),
+            });
+        }
+        let account = parse_tlv(require("26")?, "26")?;
+        let mcc = require("52")?;
+        if mcc.len() != 4 || !mcc.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(PixError::InvalidValue {
+                tag: "52".to_string(),
+                value: mcc.to_string(),
+            });
+        }
+        expect_value("53", require("53")?, "986")?;
+        expect_value("58", require("58")?, "BR")?;
+
+        let get_account = |tag: &str| account.iter().find(|(t, _)| *t == tag).map(|(_, v)| *v);
+
+        let gui = get_account("00").ok_or_else(|| PixError::MissingTag("26.00".to_string()))?;
+        if !gui.eq_ignore_ascii_case(PIX_GUI) {
+            return Err(PixError::InvalidValue {
+                tag: "26.00".to_string(),
+                value: gui.to_string(),
+            });
+        }
+
+        let chave = get_account("01").ok_or_else(|| PixError::MissingTag("26.01".to_string()))?;
+        let mut pix = PixPayload::new(chave, require("59")?, require("60")?)?;
+
+        if let Some(descricao) = get_account("02") {
+            pix = pix.with_descricao(descricao)?;
+        }
+
+        if let Some(valor) = get("54") {
+            let invalid = || PixError::InvalidValue {
+                tag: "54".to_string(),
+                value: valor.to_string(),
+            };
+            if !valor.bytes().all(|b| b.is_ascii_digit() || b == b'.') {
+                return Err(invalid());
+            }
+            let valor = Decimal::parse_with(valor, NumberLocale::EnUs).map_err(|_| invalid())?;
+            pix = pix.with_valor(valor)?;
+        }
+
+        if let Some(additional) = get("62") {
+            let additional = parse_tlv(additional, "62")?;
+            if let Some((_, txid)) = additional.iter().find(|(tag, _)| *tag == "05") {
+                if *txid != "***" {
+                    pix = pix.with_txid(txid)?;
+                }
+            }
+        }
+
+        Ok(pix)
+    }
+
+    /// Value of tag 26: GUI, key and description.
+    fn merchant_account(&self) -> String {
+        let mut account = tlv("00", PIX_GUI) + &tlv("01", &self.chave);
+        if let Some(descricao) = &self.descricao {
+            account += &tlv("02", descricao);
+        }
+        account
+    }
+}
+
+impl FromStr for PixPayload {
+    type Err = PixError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PixPayload::parse(s)
+    }
+}
+
+impl fmt::Display for PixPayload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_payload())
+    }
+}
+
+/**
+CRC16-CCITT (polynomial 0x1021, initial value 0xFFFF) used by the BR Code.
+
+```
+    use claudiofsr_lib::crc16_ccitt;
+
+    assert_eq!(crc16_ccitt(b"123456789"), 0x29B1);
+```
+*/
+pub fn crc16_ccitt(bytes: &[u8]) -> u16 {
+    let mut crc: u16 = 0xFFFF;
+
+    for &byte in bytes {
+        crc ^= u16::from(byte) << 8;
+        for _ in 0..8 {
+            crc = match crc & 0x8000 {
+                0 => crc << 1,
+                _ => (crc << 1) ^ 0x1021,
+            };
+        }
+    }
+
+    crc
+}
+
+/**
+Text allowed in the BR Code: accents removed, only printable ASCII,
+whitespace collapsed, truncated to `max_len` characters.
+
+```
+    use claudiofsr_lib::normalize_pix_text;
+
+    assert_eq!(normalize_pix_text("  Padaria  Pão de Açúcar™ ", 15), "Padaria Pao de");
+```
+*/
+pub fn normalize_pix_text(text: &str, max_len: usize) -> String {
+    let ascii: String = text
+        .remove_accents()
+        .chars()
+        .map(|ch| match ch {
+            ' '..='~' => ch,
+            _ => ' ',
+        })
+        .collect();
+
+    ascii
+        .replace_multiple_whitespaces()
+        .trim()
+        .get_first_n_chars(max_len)
+        .trim_end()
+        .to_string()
+}
+
+fn tlv(tag: &str, value: &str) -> String {
+    format!("{tag}{:02}{value}", value.chars().count())
+}
+
+fn expect_value(tag: &str, value: &str, expected: &str) -> Result<(), PixError> {
+    match value == expected {
+        true => Ok(()),
+        false => Err(PixError::InvalidValue {
+            tag: tag.to_string(),
+            value: value.to_string(),
+        }),
+    }
+}
+
+/// Split the TLV fields: 2-digit tag, 2-digit length and the value.
+fn parse_tlv<'a>(text: &'a str, parent: &str) -> Result<Vec<(&'a str, &'a str)>, PixError> {
+    let name = |tag: &str| match parent {
+        "" => tag.to_string(),
+        _ => format!("{parent}.{tag}"),
+    };
+
+    let mut fields = Vec::new();
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        let tag = rest.get(..2).unwrap_or(rest);
+        if tag.len() < 2 || !tag.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(PixError::InvalidTag(name(tag.get_first_n_chars(2))));
+        }
+
+        let length = match rest.get(2..4) {
+            Some(length) => length,
+            None if rest.len() < 4 => return Err(PixError::Truncated { tag: name(tag) }),
+            None => {
+                return Err(PixError::InvalidLength {
+                    tag: name(tag),
+                    length: (&rest[2..]).get_first_n_chars(2).to_string(),
+                })
+            }
+        };
+
+        let len: usize = match length.bytes().all(|b| b.is_ascii_digit()) {
+            true => length.parse().unwrap_or_default(),
+            false => {
+                return Err(PixError::InvalidLength {
+                    tag: name(tag),
+                    length: length.to_string(),
+                })
+            }
+        };
+
+        let value_and_rest = &rest[4..];
+        let end = match value_and_rest.char_indices().nth(len) {
+            Some((index, _)) => index,
+            None if value_and_rest.chars().count() == len => value_and_rest.len(),
+            None => return Err(PixError::Truncated { tag: name(tag) }),
+        };
+
+        fields.push((tag, &value_and_rest[..end]));
+        rest = &value_and_rest[end..];
+    }
+
+    Ok(fields)
+}
+
+#[cfg(test)]
+mod pix_tests {
+    use super::*;
+
+    // cargo test -- --show-output pix_tests
+
+    const PAYLOAD: &str = "00020126580014br.gov.bcb.pix0136123e4567-e12b-12d1-a456-4266554400005204000053039865802BR5913Fulano de Tal6008BRASILIA62070503***63041D3D";
+
+    #[test]
+    fn build_and_parse() {
+        let pix = PixPayload::new("+5561912345678", "Empresa Ltda", "Brasília")
+            .unwrap()
+            .with_descricao("Reembolso de despesas")
+            .unwrap()
+            .with_valor(Decimal::new(123456, 2))
+            .unwrap()
+            .with_txid("ABC123")
+            .unwrap();
+
+        let payload = pix.to_payload();
+        assert!(payload.contains("0221Reembolso de despesas"));
+        assert!(payload.contains("54071234.56"));
+        assert!(payload.contains("6008Brasilia"));
+        assert!(payload.contains("62100506ABC123"));
+
+        let parsed = PixPayload::parse(&payload).unwrap();
+        assert_eq!(parsed, pix);
+        assert_eq!(parsed.txid(), "ABC123");
+        assert_eq!(parsed.descricao(), Some("Reembolso de despesas"));
+
+        let pix = PixPayload::parse(PAYLOAD).unwrap();
+        assert_eq!(pix.chave(), "123e4567-e12b-12d1-a456-426655440000");
+        assert_eq!(pix.valor(), None);
+        assert_eq!(pix.txid(), "***");
+    }
+
+    #[test]
+    fn malformed_tags() {
+        let with_crc = |data: &str| format!("{data}{:04X}", crc16_ccitt(data.as_bytes()));
+
+        let error = PixPayload::parse(&PAYLOAD.replace("1D3D", "1D3E")).unwrap_err();
+        assert_eq!(
+            error,
+            PixError::InvalidCrc {
+                expected: "1D3D".to_string(),
+                found: "1D3E".to_string()
+            }
+        );
+
+        let payload = with_crc("000201260400146304");
+        assert_eq!(
+            PixPayload::parse(&payload),
+            Err(PixError::Truncated {
+                tag: "26.00".to_string()
+            })
+        );
+
+        let payload = with_crc("0002012604XX016304");
+        assert_eq!(
+            PixPayload::parse(&payload),
+            Err(PixError::InvalidTag("26.XX".to_string()))
+        );
+
+        let payload = with_crc("00020126180014br.gov.bcb.pix52A400006304");
+        assert_eq!(
+            PixPayload::parse(&payload),
+            Err(PixError::InvalidLength {
+                tag: "52".to_string(),
+                length: "A4".to_string()
+            })
+        );
+
+        let payload = PAYLOAD.replace("5802BR", "5802US");
+        let payload = with_crc(&payload[..payload.len() - 4]);
+        assert_eq!(
+            PixPayload::parse(&payload),
+            Err(PixError::InvalidValue {
+                tag: "58".to_string(),
+                value: "US".to_string()
+            })
+        );
+
+        for (tag, value, from, to) in [
+            ("52", "ABCD", "52040000", "5204ABCD"),
+            ("01", "13", "00020126", "00020101021326"),
+        ] {
+            let payload = PAYLOAD.replace(from, to);
+            let payload = with_crc(&payload[..payload.len() - 4]);
+            assert_eq!(
+                PixPayload::parse(&payload),
+                Err(PixError::InvalidValue {
+                    tag: tag.to_string(),
+                    value: value.to_string()
+                })
+            );
+        }
+
+        let payload = PAYLOAD.replace("00020126", "00020101021126");
+        let payload = with_crc(&payload[..payload.len() - 4]);
+        assert!(PixPayload::parse(&payload).is_ok());
+
+        let payload = PAYLOAD.replace("0136123e4567-e12b-12d1-a456-426655440000", "");
+        let payload = payload.replace("26580014", "26180014");
+        let payload = with_crc(&payload[..payload.len() - 4]);
+        assert_eq!(
+            PixPayload::parse(&payload),
+            Err(PixError::MissingTag("26.01".to_string()))
+        );
+    }
+
+    #[test]
+    fn builder_limits() {
+        let pix = PixPayload::new("chave", "Nome", "Cidade").unwrap();
+
+        assert_eq!(
+            pix.clone().with_valor(Decimal::ZERO),
+            Err(PixError::InvalidValue {
+                tag: "54".to_string(),
+                value: "0.00".to_string()
+            })
+        );
+        assert_eq!(
+            pix.clone().with_valor(Decimal::new(10_000_000_000_000, 2)),
+            Err(PixError::ValueTooLong {
+                tag: "54".to_string(),
+                max: 13
+            })
+        );
+        assert_eq!(
+            pix.clone().with_txid("ABC-123"),
+            Err(PixError::InvalidValue {
+                tag: "62.05".to_string(),
+                value: "ABC-123".to_string()
+            })
+        );
+        assert!(pix.clone().with_descricao(&"x".repeat(80)).is_err());
+        assert_eq!(
+            PixPayload::new("chave", "  ", "Cidade"),
+            Err(PixError::MissingTag("59".to_string()))
+        );
+    }
+}
@@ -1,3 +1,6 @@
+use crate::Decimal;
+use std::cmp::Ordering;
+
 /// Round floating numbers (f32 or f64)
 pub trait RoundFloat<T> {
     /**
@@ -17,6 +20,8 @@ pub trait RoundFloat<T> {
 
     Neither is contradicting the IEEE-754 standard, which defines and allows for both.
 
+    For other rounding modes, and values like 1.005 that are not exact in binary, see [`RoundWith`].
+
     Examples:
     ```
         use claudiofsr_lib::RoundFloat;
@@ -119,6 +124,104 @@ where
     }
 }
 
+/// How to round a number to a number of decimal places.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RoundingMode {
+    /// Nearest value, ties away from zero: 2.5 -> 3 and -2.5 -> -3.
+    #[default]
+    HalfUp,
+    /// Nearest value, ties to the even digit (ABNT NBR 5891): 2.5 -> 2 and 3.5 -> 4.
+    HalfEven,
+    /// Nearest value, ties toward zero: 2.5 -> 2 and -2.5 -> -2.
+    HalfDown,
+    /// Toward zero: 2.9 -> 2 and -2.9 -> -2.
+    Truncate,
+    /// Toward negative infinity: 2.9 -> 2 and -2.1 -> -3.
+    Floor,
+    /// Toward positive infinity: 2.1 -> 3 and -2.9 -> -2.
+    Ceiling,
+}
+
+impl RoundingMode {
+    /// Integer division `value / divisor`, rounded with this mode.
+    ///
+    /// The divisor must be positive.
+    pub(crate) fn divide(self, value: i128, divisor: i128) -> i128 {
+        let quotient = value / divisor;
+        let remainder = value % divisor;
+
+        if remainder == 0 {
+            return quotient;
+        }
+
+        // Away from zero.
+        let away = quotient + value.signum();
+        let half = (remainder.unsigned_abs() * 2).cmp(&divisor.unsigned_abs());
+
+        match (self, half) {
+            (RoundingMode::Truncate, _) => quotient,
+            (RoundingMode::Floor, _) if value < 0 => away,
+            (RoundingMode::Ceiling, _) if value > 0 => away,
+            (RoundingMode::Floor | RoundingMode::Ceiling, _) => quotient,
+            (_, Ordering::Greater) => away,
+            (_, Ordering::Less) => quotient,
+            (RoundingMode::HalfUp, Ordering::Equal) => away,
+            (RoundingMode::HalfDown, Ordering::Equal) => quotient,
+            (RoundingMode::HalfEven, Ordering::Equal) if quotient % 2 == 0 => quotient,
+            (RoundingMode::HalfEven, Ordering::Equal) => away,
+        }
+    }
+}
+
+/// Round numbers with a selectable [`RoundingMode`].
+pub trait RoundWith {
+    /**
+    Round to `places` decimal places with the rounding mode.
+
+    Floating-point numbers are rounded by their shortest decimal representation,
+    so 1.005 is rounded as the decimal 1.005, not as the binary 1.00499999999999989...
+
+    Example:
+    ```
+        use claudiofsr_lib::{Decimal, RoundFloat, RoundWith, RoundingMode};
+
+        assert_eq!(1.005.round_float(2), 1.0);
+        assert_eq!(1.005.round_with(2, RoundingMode::HalfUp), 1.01);
+
+        assert_eq!(2.345_f64.round_with(2, RoundingMode::HalfEven), 2.34);
+        assert_eq!(2.355_f64.round_with(2, RoundingMode::HalfEven), 2.36);
+        assert_eq!(2.3451_f64.round_with(2, RoundingMode::HalfEven), 2.35);
+
+        assert_eq!((-1.234_f32).round_with(1, RoundingMode::Floor), -1.3);
+        assert_eq!((-1.234_f32).round_with(1, RoundingMode::Ceiling), -1.2);
+
+        let value = Decimal::new(-2005, 3);
+        assert_eq!(value.round_with(2, RoundingMode::HalfDown).to_string(), "-2.00");
+        assert_eq!(value.round_with(2, RoundingMode::Truncate).to_string(), "-2.00");
+    ```
+    */
+    fn round_with(self, places: u32, mode: RoundingMode) -> Self;
+}
+
+impl RoundWith for f64 {
+    fn round_with(self, places: u32, mode: RoundingMode) -> f64 {
+        match Decimal::from_f64(self) {
+            Some(decimal) => decimal.round_with(places, mode).to_f64(),
+            // NaN, infinity, or so large that it has no decimal places.
+            None => self,
+        }
+    }
+}
+
+impl RoundWith for f32 {
+    fn round_with(self, places: u32, mode: RoundingMode) -> f32 {
+        match Decimal::from_f32(self) {
+            Some(decimal) => decimal.round_with(places, mode).to_f32(),
+            None => self,
+        }
+    }
+}
+
 /// Try Convert Extension
 pub trait TryConvertExtension<T> {
     /**
@@ -277,4 +380,39 @@ mod round_numbers {
         let result = f64::INFINITY.round_float(decimal_places);
         assert!(result.is_infinite());
     }
+
+    #[test]
+    fn round_with_modes() {
+        // cargo test -- --show-output round_with_modes
+        let modes = [
+            RoundingMode::HalfUp,
+            RoundingMode::HalfEven,
+            RoundingMode::HalfDown,
+            RoundingMode::Truncate,
+            RoundingMode::Floor,
+            RoundingMode::Ceiling,
+        ];
+
+        let cases: [(f64, [f64; 6]); 6] = [
+            (2.5, [3.0, 2.0, 2.0, 2.0, 2.0, 3.0]),
+            (3.5, [4.0, 4.0, 3.0, 3.0, 3.0, 4.0]),
+            (-2.5, [-3.0, -2.0, -2.0, -2.0, -3.0, -2.0]),
+            (2.51, [3.0, 3.0, 3.0, 2.0, 2.0, 3.0]),
+            (-2.49, [-2.0, -2.0, -2.0, -2.0, -3.0, -2.0]),
+            (7.0, [7.0; 6]),
+        ];
+
+        for (number, expected) in cases {
+            for (mode, result) in modes.iter().zip(expected) {
+                assert_eq!(number.round_with(0, *mode), result, "{number} {mode:?}");
+                let number = number as f32 / 100.0;
+                let result = result as f32 / 100.0;
+                assert_eq!(number.round_with(2, *mode), result, "{number} {mode:?}");
+            }
+        }
+
+        assert!(f64::NAN.round_with(2, RoundingMode::HalfEven).is_nan());
+        assert_eq!(f64::MAX.round_with(2, RoundingMode::Floor), f64::MAX);
+        assert_eq!(1e-30_f64.round_with(2, RoundingMode::Ceiling), 0.01);
+    }
 }
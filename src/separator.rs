@@ -1,7 +1,12 @@
+use crate::{FormatNumber, NumberFormat};
+
 // https://stackoverflow.com/questions/72888582/choose-thousands-and-decimal-separators-for-f64-in-rust
 // https://stackoverflow.com/questions/57029974/how-to-split-string-into-chunks-in-rust-to-insert-spaces
 /// Add the thousands separator to float64 with the specified decimal number.
 ///
+/// Uses the [`NumberFormat::pt_br`] preset, but keeps the minus sign of negative
+/// values that round to zero: `-0.0001` is formatted as `"-0,00"`.
+///
 /// Example:
 /// ```
 ///     use claudiofsr_lib::thousands_separator;
@@ -13,28 +18,16 @@
 ///     assert_eq!(result, "-2.987.954.368,37");
 /// ```
 pub fn thousands_separator(value: f64, decimal: usize) -> String {
-    let abs_value: f64 = value.abs(); // absolute value
-    let round: String = format!("{abs_value:0.decimal$}");
-
-    // integer and fractional part of f64 numbers,
-    let integer: &str = &round[..(round.len() - decimal - 1)];
-    let fraction: &str = &round[(round.len() - decimal)..];
+    let formatted = NumberFormat::pt_br().decimals(decimal).format(value);
 
-    let decimal_sep: &str = ",";
-    let thousands_sep: char = '.';
-
-    let integer_splitted: String = split_and_insert(integer, thousands_sep);
-
-    if value.is_sign_negative() {
-        "-".to_string() + &integer_splitted + decimal_sep + fraction
+    if value.is_sign_negative() && !formatted.starts_with('-') {
+        "-".to_string() + &formatted
     } else {
-        integer_splitted + decimal_sep + fraction
+        formatted
     }
 }
 
-fn split_and_insert(integer: &str, insert: char) -> String {
-    let group_size = 3;
-
+pub(crate) fn split_and_insert(integer: &str, insert: char, group_size: usize) -> String {
     let string_splitted: String = integer
         .chars()
         .enumerate()
@@ -80,6 +73,8 @@ fn split_and_insert_alternative(integer: &str, insert: char) -> String {
 
 /// Add the thousands separator to float64 with the specified decimal number.
 ///
+/// Same as [`thousands_separator`].
+///
 /// Example:
 /// ```
 ///     use claudiofsr_lib::thousands_separator_v2;
@@ -93,46 +88,30 @@ fn split_and_insert_alternative(integer: &str, insert: char) -> String {
 ///
 /// <https://stackoverflow.com/questions/26998485/is-it-possible-to-print-a-number-formatted-with-thousand-separator-in-rust>
 pub fn thousands_separator_v2(value: f64, decimal: usize) -> String {
-    let round: String = format!("{value:0.decimal$}");
-
-    let decimal_sep: u8 = b',';
-    let thousands_sep: char = '.';
-    let group_size: usize = 3;
-
-    // Position of the `.`
-    let dot_position: usize = round.bytes().position(|c| c == b'.').unwrap_or(round.len());
-    // Is the number negative (starts with `-`)?
-    let negative: bool = value.is_sign_negative();
-    // Number of integer digits remaning (between the `-` or start and the `.`).
-    let mut integer_digits_remaining = dot_position - usize::from(negative);
-    // Output. Add capacity for commas. It's a slight over-estimate but that's fine.
-    let mut formatted = String::with_capacity(round.len() + integer_digits_remaining / group_size);
-
-    // We can iterate on bytes because everything must be ASCII. Slightly faster.
-    for (index, mut byte) in round.bytes().enumerate() {
-        match byte {
-            b'.' => {
-                // Change the decimal separator from '.' for decimal_sep
-                byte = decimal_sep;
-            }
-            b'0'..=b'9' => {
-                // Possibly add a thousands_sep.
-                if integer_digits_remaining > 0 {
-                    // Don't add a thousands_sep at the start of the string.
-                    // usize::from(negative); // if negative { 1 } else { 0 }
-                    if index != usize::from(negative) && integer_digits_remaining % group_size == 0
-                    {
-                        formatted.push(thousands_sep);
-                    }
-                    integer_digits_remaining -= 1;
-                }
-            }
-            _ => (),
-        }
-        formatted.push(byte as char);
-    }
+    thousands_separator(value, decimal)
+}
 
-    formatted
+/// Format a number with comma as decimal separator and no thousands separator.
+///
+/// This is the format of decimal fields in SPED files.
+/// Pass a [`Decimal`](crate::Decimal) to write amounts without `f64` rounding errors.
+///
+/// Zero values are formatted without the minus sign.
+///
+/// Example:
+/// ```
+///     use claudiofsr_lib::{decimal_comma, Decimal};
+///
+///     assert_eq!(decimal_comma(-2987954368.369177, 2), "-2987954368,37");
+///     assert_eq!(decimal_comma(1234.56, 0), "1235");
+///     assert_eq!(decimal_comma(-0.001, 2), "0,00");
+///     assert_eq!(decimal_comma(Decimal::new(1005, 3), 2), "1,01");
+/// ```
+pub fn decimal_comma<N: FormatNumber>(value: N, decimal: usize) -> String {
+    NumberFormat::pt_br()
+        .thousands_separator(None)
+        .decimals(decimal)
+        .format(value)
 }
 
 #[cfg(test)]
@@ -156,6 +135,9 @@ mod functions {
             (12345.54321, 8),
             (-0.15, 4),
             (1234566.996, 2),
+            (1234.5, 0),
+            (-0.0001, 2),
+            (-0.0, 2),
         ];
 
         let result: Vec<String> = tuples
@@ -171,6 +153,9 @@ mod functions {
             "12.345,54321000",
             "-0,1500",
             "1.234.567,00",
+            "1.234",
+            "-0,00",
+            "-0,00",
         ];
 
         for ((n, d), r) in tuples.iter().zip(&result) {
@@ -178,7 +163,18 @@ mod functions {
         }
 
         assert_eq!(valid, result);
+        assert_eq!(thousands_separator_v2(-0.0001, 2), "-0,00");
 
         Ok(())
     }
+
+    #[test]
+    fn test_decimal_comma() {
+        // cargo test -- --show-output test_decimal_comma
+
+        assert_eq!(decimal_comma(1234566.996, 2), "1234567,00");
+        assert_eq!(decimal_comma(-0.15, 4), "-0,1500");
+        assert_eq!(decimal_comma(-0.0, 2), "0,00");
+        assert_eq!(decimal_comma(7.0, 0), "7");
+    }
 }
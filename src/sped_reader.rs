@@ -0,0 +1,338 @@
+use crate::{open_file, SpedError, SpedRecord};
+use std::{
+    fmt,
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::Path,
+};
+
+/// Byte order mark of UTF-8 files.
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Characters of Windows-1252 from 0x80 to 0x9F.
+///
+/// The 5 undefined bytes (0x81, 0x8D, 0x8F, 0x90, 0x9D) keep their Latin-1 value.
+/// From 0xA0 to 0xFF, Windows-1252 and Latin-1 are equal to Unicode.
+pub(crate) const WINDOWS_1252: [char; 32] = [
+    '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8D}', 'Ž', '\u{8F}',
+    '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9D}', 'ž', 'Ÿ',
+];
+
+/// Text encoding of a SPED file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SpedEncoding {
+    /// UTF-8, with or without BOM.
+    Utf8,
+    /// ISO-8859-1 (Latin-1), read as its superset Windows-1252.
+    Latin1,
+}
+
+/// Errors returned by [`SpedReader`].
+#[derive(Debug)]
+pub enum SpedReadError {
+    /// Failure reading the file.
+    Io(io::Error),
+    /// A line declared or detected as UTF-8 has invalid bytes.
+    InvalidUtf8 { line_number: usize },
+    /// A line is not a valid SPED record.
+    Record(SpedError),
+}
+
+impl fmt::Display for SpedReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpedReadError::Io(error) => write!(f, "failed to read SPED file: {error}"),
+            SpedReadError::InvalidUtf8 { line_number } => {
+                write!(f, "line {line_number}: invalid UTF-8")
+            }
+            SpedReadError::Record(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for SpedReadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SpedReadError::Io(error) => Some(error),
+            SpedReadError::InvalidUtf8 { .. } => None,
+            SpedReadError::Record(error) => Some(error),
+        }
+    }
+}
+
+impl From<io::Error> for SpedReadError {
+    fn from(error: io::Error) -> Self {
+        SpedReadError::Io(error)
+    }
+}
+
+impl From<SpedError> for SpedReadError {
+    fn from(error: SpedError) -> Self {
+        SpedReadError::Record(error)
+    }
+}
+
+/**
+Streaming reader of SPED files, one line at a time.
+
+Each line is decoded to UTF-8 and split into a [`SpedRecord`] that borrows
+the reader's buffer, so records must be consumed before reading the next one.
+
+Without an explicit encoding, a BOM selects UTF-8; otherwise, the first line
+with non-ASCII bytes decides between UTF-8 and Latin-1 (Windows-1252).
+
+CRLF line terminators and blank lines are accepted.
+Reading stops after the `|9999|` record, skipping the digital signature.
+
+Example:
+```
+    use claudiofsr_lib::{SpedEncoding, SpedReader};
+
+    // "Ação" in Latin-1, followed by the digital signature.
+    let bytes: &[u8] = b"|0000|A\xE7\xE3o|\r\n|9999|2|\r\n\r\nSBRCAAEPDR...";
+    let mut reader = SpedReader::new(bytes);
+
+    let mut registers = Vec::new();
+    while let Some(result) = reader.next_record() {
+        let (line_number, record) = result.unwrap();
+        registers.push((line_number, record.register().to_string()));
+        if line_number == 1 {
+            assert_eq!(record.field_str(2).unwrap(), "Ação");
+        }
+    }
+
+    assert_eq!(registers, [(1, "0000".to_string()), (2, "9999".to_string())]);
+    assert_eq!(reader.encoding(), Some(SpedEncoding::Latin1));
+```
+
+Reading a file:
+```no_run
+    use claudiofsr_lib::{MyResult, SpedReader};
+
+    fn main() -> MyResult<()> {
+        let mut reader = SpedReader::open("efd_contribuicoes.txt")?;
+
+        while let Some(result) = reader.next_record() {
+            let (line_number, record) = result?;
+            if record.register() == "C170" {
+                println!("{line_number}: {:?}", record.field_decimal(7)?);
+            }
+        }
+
+        Ok(())
+    }
+```
+*/
+pub struct SpedReader<R> {
+    reader: R,
+    encoding: Option<SpedEncoding>,
+    bytes: Vec<u8>,
+    line: String,
+    line_number: usize,
+    finished: bool,
+}
+
+impl SpedReader<BufReader<File>> {
+    /// Open a SPED file with [`open_file`], detecting the encoding.
+    pub fn open<P>(path: P) -> Result<Self, io::Error>
+    where
+        P: AsRef<Path>,
+    {
+        let file = open_file(path)?;
+        Ok(SpedReader::new(BufReader::new(file)))
+    }
+}
+
+impl<R: BufRead> SpedReader<R> {
+    /// Read SPED lines from any buffered reader, detecting the encoding.
+    pub fn new(reader: R) -> Self {
+        SpedReader {
+            reader,
+            encoding: None,
+            bytes: Vec::new(),
+            line: String::new(),
+            line_number: 0,
+            finished: false,
+        }
+    }
+
+    /// Read with a known encoding instead of detecting it.
+    pub fn with_encoding(mut self, encoding: SpedEncoding) -> Self {
+        self.encoding = Some(encoding);
+        self
+    }
+
+    /// The declared or detected encoding, if already known.
+    pub fn encoding(&self) -> Option<SpedEncoding> {
+        self.encoding
+    }
+
+    /// Number of the last line read, starting at 1.
+    pub fn line_number(&self) -> usize {
+        self.line_number
+    }
+
+    /// Read the next non-blank line as `(line_number, record)`.
+    ///
+    /// Returns `None` at the end of the file or after the `|9999|` record.
+    pub fn next_record(&mut self) -> Option<Result<(usize, SpedRecord<'_>), SpedReadError>> {
+        loop {
+            if self.finished {
+                return None;
+            }
+
+            match self.read_line() {
+                Ok(true) => (),
+                Ok(false) => {
+                    self.finished = true;
+                    return None;
+                }
+                Err(error) => {
+                    self.finished = true;
+                    return Some(Err(error));
+                }
+            }
+
+            if !self.line.trim().is_empty() {
+                break;
+            }
+        }
+
+        let line_number = self.line_number;
+
+        match SpedRecord::parse(&self.line, line_number) {
+            Ok(record) => {
+                if record.register() == "9999" {
+                    self.finished = true;
+                }
+                Some(Ok((line_number, record)))
+            }
+            Err(error) => Some(Err(error.into())),
+        }
+    }
+
+    /// Read and decode the next line into `self.line`.
+    ///
+    /// Returns `false` at the end of the file.
+    fn read_line(&mut self) -> Result<bool, SpedReadError> {
+        self.bytes.clear();
+        self.line.clear();
+
+        if self.reader.read_until(b'\n', &mut self.bytes)? == 0 {
+            return Ok(false);
+        }
+
+        self.line_number += 1;
+
+        let mut bytes: &[u8] = &self.bytes;
+
+        if self.line_number == 1 {
+            if let Some(rest) = bytes.strip_prefix(UTF8_BOM) {
+                bytes = rest;
+                self.encoding = Some(SpedEncoding::Utf8);
+            }
+        }
+
+        while let Some((b'\n' | b'\r', rest)) = bytes.split_last() {
+            bytes = rest;
+        }
+
+        if self.encoding.is_none() && !bytes.is_ascii() {
+            let encoding = match std::str::from_utf8(bytes) {
+                Ok(_) => SpedEncoding::Utf8,
+                Err(_) => SpedEncoding::Latin1,
+            };
+            self.encoding = Some(encoding);
+        }
+
+        match self.encoding {
+            Some(SpedEncoding::Latin1) => {
+                self.line
+                    .extend(bytes.iter().map(|&b| decode_windows_1252(b)));
+            }
+            _ => match std::str::from_utf8(bytes) {
+                Ok(text) => self.line.push_str(text),
+                Err(_) => {
+                    return Err(SpedReadError::InvalidUtf8 {
+                        line_number: self.line_number,
+                    })
+                }
+            },
+        }
+
+        Ok(true)
+    }
+}
+
+/// Decode one byte of Windows-1252.
+fn decode_windows_1252(byte: u8) -> char {
+    match byte {
+        0x80..=0x9F => WINDOWS_1252[usize::from(byte - 0x80)],
+        _ => char::from(byte),
+    }
+}
+
+#[cfg(test)]
+mod sped_reader_tests {
+    use super::*;
+
+    // cargo test -- --show-output sped_reader_tests
+
+    fn registers<R: BufRead>(reader: &mut SpedReader<R>) -> Vec<(usize, String)> {
+        let mut output = Vec::new();
+        while let Some(result) = reader.next_record() {
+            let (line_number, record) = result.unwrap();
+            output.push((line_number, record.to_string()));
+        }
+        output
+    }
+
+    #[test]
+    fn read_utf8_with_bom() {
+        let bytes = "\u{FEFF}|0000|Ação|\n\n|0001|0|\n|9999|3|\n\n\n".as_bytes();
+        let mut reader = SpedReader::new(bytes);
+
+        assert_eq!(
+            registers(&mut reader),
+            [
+                (1, "|0000|Ação|".to_string()),
+                (3, "|0001|0|".to_string()),
+                (4, "|9999|3|".to_string()),
+            ]
+        );
+        assert_eq!(reader.encoding(), Some(SpedEncoding::Utf8));
+    }
+
+    #[test]
+    fn read_windows_1252() {
+        // 0x80: euro sign, 0x93 and 0x94: quotation marks, 0xC7: 'Ç'.
+        let bytes: &[u8] = b"|0000|\x80 \x93A\xC7\xC3O\x94|\r\n|9999|2|\r\n";
+        let mut reader = SpedReader::new(bytes);
+
+        assert_eq!(registers(&mut reader)[0].1, "|0000|€ “AÇÃO”|");
+        assert_eq!(reader.encoding(), Some(SpedEncoding::Latin1));
+    }
+
+    #[test]
+    fn read_errors() {
+        let bytes: &[u8] = b"|0000|\xE7|\n|0001|";
+        let mut reader = SpedReader::new(bytes).with_encoding(SpedEncoding::Utf8);
+
+        let error = reader.next_record().unwrap().unwrap_err();
+        assert!(matches!(
+            error,
+            SpedReadError::InvalidUtf8 { line_number: 1 }
+        ));
+        assert!(reader.next_record().is_none());
+
+        let bytes: &[u8] = b"|0000|\n0001|\n";
+        let mut reader = SpedReader::new(bytes);
+
+        assert!(reader.next_record().unwrap().is_ok());
+        let error = reader.next_record().unwrap().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2: line must start and end with '|'"
+        );
+    }
+}
@@ -0,0 +1,325 @@
+use crate::{get_naive_date, Decimal, NumberLocale};
+use chrono::NaiveDate;
+use std::{fmt, str::FromStr};
+
+/// What went wrong while reading a SPED line or one of its fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpedErrorKind {
+    /// The line does not start and end with `|`.
+    MissingDelimiter,
+    /// The register code (field 01) is not 4 ASCII alphanumeric characters.
+    InvalidRegister(String),
+    /// The requested field does not exist: the record has `count` fields.
+    FieldOutOfRange { count: usize },
+    /// The field is not a number with comma decimals, like "1234,56".
+    InvalidDecimal(String),
+    /// The field is not a valid date in the format DDMMYYYY.
+    InvalidDate(String),
+    /// The field is not an integer in the expected range.
+    InvalidInteger(String),
+}
+
+impl fmt::Display for SpedErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpedErrorKind::MissingDelimiter => write!(f, "line must start and end with '|'"),
+            SpedErrorKind::InvalidRegister(reg) => write!(f, "invalid register code: {reg:?}"),
+            SpedErrorKind::FieldOutOfRange { count } => {
+                write!(f, "field does not exist, the record has {count} fields")
+            }
+            SpedErrorKind::InvalidDecimal(value) => write!(f, "invalid decimal: {value:?}"),
+            SpedErrorKind::InvalidDate(value) => write!(f, "invalid date: {value:?}"),
+            SpedErrorKind::InvalidInteger(value) => write!(f, "invalid integer: {value:?}"),
+        }
+    }
+}
+
+/// Error located by line number and, when applicable, field index.
+///
+/// Both are 1-based, as in the SPED layouts, where field 01 is `REG`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpedError {
+    pub line_number: usize,
+    pub field_index: Option<usize>,
+    pub kind: SpedErrorKind,
+}
+
+impl fmt::Display for SpedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.field_index {
+            Some(index) => write!(
+                f,
+                "line {}, field {:02}: {}",
+                self.line_number, index, self.kind
+            ),
+            None => write!(f, "line {}: {}", self.line_number, self.kind),
+        }
+    }
+}
+
+impl std::error::Error for SpedError {}
+
+/**
+A line of a SPED file (EFD-Contribuições, EFD-ICMS-IPI), like `|C170|1|foo|...|`.
+
+The fields borrow the line: nothing is copied.
+
+Fields are numbered from 1, as in the SPED layouts: field 01 is the register code.
+
+Empty fields are common in SPED files, so the typed accessors return `Ok(None)` for them.
+
+Example:
+```
+    use claudiofsr_lib::{Decimal, SpedRecord};
+    use chrono::NaiveDate;
+
+    let line = "|C100|0|1|P001|55|00|001|123||01022024|01022024|1234,56|";
+    let record = SpedRecord::parse(line, 7).unwrap();
+
+    assert_eq!(record.register(), "C100");
+    assert_eq!(record.field_count(), 12);
+    assert_eq!(record.field_str(4).unwrap(), "P001");
+    assert_eq!(record.field_u16(5).unwrap(), Some(55));
+    assert_eq!(record.field_str(9).unwrap(), "");
+    assert_eq!(record.field_date(9).unwrap(), None);
+    assert_eq!(record.field_date(10).unwrap(), NaiveDate::from_ymd_opt(2024, 2, 1));
+    assert_eq!(record.field_decimal(12).unwrap(), Some(Decimal::new(123456, 2)));
+
+    let error = record.field_str(13).unwrap_err();
+    assert_eq!(error.to_string(), "line 7, field 13: field does not exist, the record has 12 fields");
+```
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpedRecord<'a> {
+    line_number: usize,
+    fields: Vec<&'a str>,
+}
+
+impl<'a> SpedRecord<'a> {
+    /// Split a line into fields.
+    ///
+    /// The line terminator (`\n` or `\r\n`) is ignored.
+    pub fn parse(line: &'a str, line_number: usize) -> Result<Self, SpedError> {
+        let error = |kind| SpedError {
+            line_number,
+            field_index: None,
+            kind,
+        };
+
+        let line = line.trim_end_matches(['\r', '\n']);
+
+        let content = line
+            .strip_prefix('|')
+            .and_then(|l| l.strip_suffix('|'))
+            .ok_or(error(SpedErrorKind::MissingDelimiter))?;
+
+        let fields: Vec<&str> = content.split('|').collect();
+
+        let register = fields[0];
+        if register.len() != 4 || !register.bytes().all(|b| b.is_ascii_alphanumeric()) {
+            return Err(error(SpedErrorKind::InvalidRegister(register.to_string())));
+        }
+
+        Ok(SpedRecord {
+            line_number,
+            fields,
+        })
+    }
+
+    /// Line number in the file, starting at 1.
+    pub fn line_number(&self) -> usize {
+        self.line_number
+    }
+
+    /// The register code (field 01), like "C170".
+    pub fn register(&self) -> &'a str {
+        self.fields[0]
+    }
+
+    /// Number of fields, including the register code.
+    pub fn field_count(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// All fields, starting with the register code.
+    pub fn fields(&self) -> &[&'a str] {
+        &self.fields
+    }
+
+    /// The field `n` (1-based) as text.
+    pub fn field_str(&self, n: usize) -> Result<&'a str, SpedError> {
+        n.checked_sub(1)
+            .and_then(|index| self.fields.get(index))
+            .copied()
+            .ok_or_else(|| {
+                self.error(
+                    n,
+                    SpedErrorKind::FieldOutOfRange {
+                        count: self.field_count(),
+                    },
+                )
+            })
+    }
+
+    /// The field `n` as an exact number with comma decimals: "1234,56" or "-0,5".
+    pub fn field_decimal(&self, n: usize) -> Result<Option<Decimal>, SpedError> {
+        let value = self.field_str(n)?;
+
+        if value.is_empty() {
+            return Ok(None);
+        }
+
+        let digits = value.strip_prefix('-').unwrap_or(value);
+        let valid = digits.bytes().filter(|&b| b == b',').count() <= 1
+            && digits.bytes().any(|b| b.is_ascii_digit())
+            && digits.bytes().all(|b| b.is_ascii_digit() || b == b',');
+
+        if !valid {
+            return Err(self.error(n, SpedErrorKind::InvalidDecimal(value.to_string())));
+        }
+
+        Decimal::parse_with(value, NumberLocale::PtBr)
+            .map(Some)
+            .map_err(|_| self.error(n, SpedErrorKind::InvalidDecimal(value.to_string())))
+    }
+
+    /// The field `n` as a date in the format DDMMYYYY.
+    pub fn field_date(&self, n: usize) -> Result<Option<NaiveDate>, SpedError> {
+        let value = self.field_str(n)?;
+
+        if value.is_empty() {
+            return Ok(None);
+        }
+
+        if value.len() != 8 || !value.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(self.error(n, SpedErrorKind::InvalidDate(value.to_string())));
+        }
+
+        get_naive_date(value)
+            .map(Some)
+            .ok_or_else(|| self.error(n, SpedErrorKind::InvalidDate(value.to_string())))
+    }
+
+    /// The field `n` as an integer, like codes and indicators.
+    pub fn field_u16(&self, n: usize) -> Result<Option<u16>, SpedError> {
+        self.field_integer(n)
+    }
+
+    /// The field `n` as an integer, like line and register counts.
+    pub fn field_u64(&self, n: usize) -> Result<Option<u64>, SpedError> {
+        self.field_integer(n)
+    }
+
+    fn field_integer<T: FromStr>(&self, n: usize) -> Result<Option<T>, SpedError> {
+        let value = self.field_str(n)?;
+
+        if value.is_empty() {
+            return Ok(None);
+        }
+
+        if !value.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(self.error(n, SpedErrorKind::InvalidInteger(value.to_string())));
+        }
+
+        value
+            .parse::<T>()
+            .map(Some)
+            .map_err(|_| self.error(n, SpedErrorKind::InvalidInteger(value.to_string())))
+    }
+
+    fn error(&self, field_index: usize, kind: SpedErrorKind) -> SpedError {
+        SpedError {
+            line_number: self.line_number,
+            field_index: Some(field_index),
+            kind,
+        }
+    }
+}
+
+impl fmt::Display for SpedRecord<'_> {
+    /// Format as a SPED line, without the line terminator: "|C170|1|foo|".
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "|{}|", self.fields.join("|"))
+    }
+}
+
+#[cfg(test)]
+mod sped_record_tests {
+    use super::*;
+
+    // cargo test -- --show-output sped_record_tests
+
+    #[test]
+    fn parse_line() {
+        let line = "|0000|006|0|||01012024|31012024|EMPRESA|\r\n";
+        let record = SpedRecord::parse(line, 1).unwrap();
+
+        assert_eq!(record.register(), "0000");
+        assert_eq!(record.field_count(), 8);
+        assert_eq!(record.field_str(2), Ok("006"));
+        assert_eq!(record.field_u16(3), Ok(Some(0)));
+        assert_eq!(record.field_str(8), Ok("EMPRESA"));
+        assert_eq!(
+            record.to_string(),
+            "|0000|006|0|||01012024|31012024|EMPRESA|"
+        );
+    }
+
+    #[test]
+    fn exact_amounts() {
+        let line = "|C170|1|P1|0,10|-0,5|1234,567|";
+        let record = SpedRecord::parse(line, 1).unwrap();
+
+        assert_eq!(record.field_decimal(4), Ok(Some(Decimal::new(10, 2))));
+        assert_eq!(record.field_decimal(5), Ok(Some(Decimal::new(-5, 1))));
+        assert_eq!(record.field_decimal(6), Ok(Some(Decimal::new(1234567, 3))));
+
+        let total: Decimal = (0..100_000)
+            .map(|_| record.field_decimal(4).unwrap().unwrap())
+            .sum();
+        assert_eq!(total, Decimal::from(10_000));
+        assert_eq!(crate::decimal_comma(total, 2), "10000,00");
+    }
+
+    #[test]
+    fn invalid_line() {
+        let error = SpedRecord::parse("C170|1|", 3).unwrap_err();
+        assert_eq!(error.kind, SpedErrorKind::MissingDelimiter);
+        assert_eq!(error.line_number, 3);
+
+        let error = SpedRecord::parse("|C17|1|", 4).unwrap_err();
+        assert_eq!(
+            error.kind,
+            SpedErrorKind::InvalidRegister("C17".to_string())
+        );
+    }
+
+    #[test]
+    fn invalid_fields() {
+        let line = "|C170|1.234,56|1,2,3|32012024|70000|abc|";
+        let record = SpedRecord::parse(line, 10).unwrap();
+
+        for n in [2, 3] {
+            let error = record.field_decimal(n).unwrap_err();
+            assert_eq!(error.field_index, Some(n));
+            assert!(matches!(error.kind, SpedErrorKind::InvalidDecimal(_)));
+        }
+
+        assert!(matches!(
+            record.field_date(4).unwrap_err().kind,
+            SpedErrorKind::InvalidDate(_)
+        ));
+        assert!(matches!(
+            record.field_u16(5).unwrap_err().kind,
+            SpedErrorKind::InvalidInteger(_)
+        ));
+        assert!(matches!(
+            record.field_u16(6).unwrap_err().kind,
+            SpedErrorKind::InvalidInteger(_)
+        ));
+        assert_eq!(
+            record.field_str(0).unwrap_err().kind,
+            SpedErrorKind::FieldOutOfRange { count: 6 }
+        );
+    }
+}
@@ -0,0 +1,688 @@
+use crate::{SpedError, SpedErrorKind, SpedReadError, SpedReader, SpedRecord};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    io::BufRead,
+};
+
+/// Parent registers of common child registers of EFD-Contribuições and EFD-ICMS-IPI.
+///
+/// A child with more than one possible parent follows the layout of each file.
+const PARENTS: &[(&str, &[&str])] = &[
+    ("0100", &["0001"]),
+    ("0110", &["0001"]),
+    ("0140", &["0001"]),
+    ("0150", &["0140", "0001"]),
+    ("0190", &["0140", "0001"]),
+    ("0200", &["0140", "0001"]),
+    ("0400", &["0140", "0001"]),
+    ("0450", &["0140", "0001"]),
+    ("0500", &["0001"]),
+    ("A010", &["A001"]),
+    ("A100", &["A010"]),
+    ("A170", &["A100"]),
+    ("C010", &["C001"]),
+    ("C100", &["C010", "C001"]),
+    ("C110", &["C100"]),
+    ("C170", &["C100"]),
+    ("C175", &["C100"]),
+    ("C180", &["C010"]),
+    ("C181", &["C180"]),
+    ("C185", &["C180"]),
+    ("C190", &["C100"]),
+    ("C500", &["C010", "C001"]),
+    ("C501", &["C500"]),
+    ("C505", &["C500"]),
+    ("D010", &["D001"]),
+    ("D100", &["D010", "D001"]),
+    ("D101", &["D100"]),
+    ("D105", &["D100"]),
+    ("D190", &["D100"]),
+    ("F010", &["F001"]),
+    ("F100", &["F010"]),
+    ("F120", &["F010"]),
+    ("F130", &["F010"]),
+    ("F600", &["F010"]),
+    ("M100", &["M001"]),
+    ("M105", &["M100"]),
+    ("M110", &["M100"]),
+    ("M200", &["M001"]),
+    ("M210", &["M200"]),
+    ("M500", &["M001"]),
+    ("M505", &["M500"]),
+    ("M510", &["M500"]),
+    ("M600", &["M001"]),
+    ("M610", &["M600"]),
+    ("9900", &["9001"]),
+];
+
+/// Structural problem found by [`SpedValidator`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpedIssueKind {
+    /// The line is not a valid record, or a count field is not a number.
+    InvalidRecord {
+        field_index: Option<usize>,
+        kind: SpedErrorKind,
+    },
+    /// The first line is not a valid `0000` record.
+    MissingHeader,
+    /// A block starts without its opening register `x001`.
+    BlockNotOpened { block: char },
+    /// A block ends without its closing register `x990`.
+    BlockNotClosed { block: char },
+    /// A child register outside of its parent, like `C170` without `C100`.
+    OrphanRecord {
+        register: String,
+        parents: Vec<String>,
+    },
+    /// The `x990` line count differs from the lines of the block.
+    WrongBlockCount {
+        block: char,
+        expected: u64,
+        found: u64,
+    },
+    /// The `9900` count differs from the records of the register.
+    WrongRegisterCount {
+        register: String,
+        expected: u64,
+        found: u64,
+    },
+    /// A register of the file has no `9900` record.
+    MissingRegisterCount { register: String },
+    /// A register has more than one `9900` record.
+    DuplicateRegisterCount { register: String },
+    /// The `9999` line count differs from the lines of the file.
+    WrongTotal { expected: u64, found: u64 },
+    /// The file does not end with `9999`.
+    MissingTotal,
+}
+
+impl fmt::Display for SpedIssueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpedIssueKind::InvalidRecord { field_index, kind } => match field_index {
+                Some(index) => write!(f, "field {index:02}: {kind}"),
+                None => write!(f, "{kind}"),
+            },
+            SpedIssueKind::MissingHeader => write!(f, "the first record must be 0000"),
+            SpedIssueKind::BlockNotOpened { block } => {
+                write!(f, "block {block} opened without {block}001")
+            }
+            SpedIssueKind::BlockNotClosed { block } => {
+                write!(f, "block {block} closed without {block}990")
+            }
+            SpedIssueKind::OrphanRecord { register, parents } => {
+                write!(
+                    f,
+                    "register {register} must follow {}",
+                    parents.join(" or ")
+                )
+            }
+            SpedIssueKind::WrongBlockCount {
+                block,
+                expected,
+                found,
+            } => write!(
+                f,
+                "block {block} has {expected} lines, but {block}990 declares {found}"
+            ),
+            SpedIssueKind::WrongRegisterCount {
+                register,
+                expected,
+                found,
+            } => write!(
+                f,
+                "register {register} appears {expected} times, but 9900 declares {found}"
+            ),
+            SpedIssueKind::MissingRegisterCount { register } => {
+                write!(f, "register {register} has no 9900 record")
+            }
+            SpedIssueKind::DuplicateRegisterCount { register } => {
+                write!(f, "register {register} has more than one 9900 record")
+            }
+            SpedIssueKind::WrongTotal { expected, found } => {
+                write!(f, "file has {expected} lines, but 9999 declares {found}")
+            }
+            SpedIssueKind::MissingTotal => write!(f, "the last record must be 9999"),
+        }
+    }
+}
+
+/// Structural problem located by line number, when it refers to a single line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpedIssue {
+    pub line_number: Option<usize>,
+    pub kind: SpedIssueKind,
+}
+
+impl fmt::Display for SpedIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line_number {
+            Some(line_number) => write!(f, "line {line_number}: {}", self.kind),
+            None => write!(f, "{}", self.kind),
+        }
+    }
+}
+
+impl From<SpedError> for SpedIssue {
+    fn from(error: SpedError) -> Self {
+        SpedIssue {
+            line_number: Some(error.line_number),
+            kind: SpedIssueKind::InvalidRecord {
+                field_index: error.field_index,
+                kind: error.kind,
+            },
+        }
+    }
+}
+
+/**
+Structural validator of SPED files.
+
+Checks:
+- `0000` as the first record and `9999` as the last one;
+- blocks opened with `x001` and closed with `x990`;
+- child registers inside their parent (`C170` after `C100`);
+- line counts of `x990` and `9999`, and register counts of `9900`.
+
+All issues are collected, sorted by line number; issues without a line come last.
+
+Example:
+```
+    use claudiofsr_lib::{SpedReader, SpedValidator};
+
+    let text = "\
+|0000|006|
+|0001|0|
+|0990|3|
+|C001|0|
+|C170|1|
+|C990|3|
+|9001|0|
+|9900|0000|1|
+|9900|0001|1|
+|9900|0990|1|
+|9900|C001|1|
+|9900|C170|1|
+|9900|C990|1|
+|9900|9001|1|
+|9900|9900|10|
+|9900|9990|1|
+|9900|9999|1|
+|9990|13|
+|9999|20|
+";
+    let mut reader = SpedReader::new(text.as_bytes());
+    let issues = SpedValidator::new().validate(&mut reader).unwrap();
+
+    let messages: Vec<String> = issues.iter().map(|issue| issue.to_string()).collect();
+    assert_eq!(
+        messages,
+        [
+            "line 5: register C170 must follow C100",
+            "line 19: file has 19 lines, but 9999 declares 20",
+        ]
+    );
+```
+*/
+#[derive(Debug, Clone)]
+pub struct SpedValidator {
+    parents: HashMap<String, Vec<String>>,
+    issues: Vec<SpedIssue>,
+    /// Open registers, from the block opening to the last record.
+    stack: Vec<String>,
+    /// Block opened by `x001` and not yet closed by `x990`.
+    open_block: Option<char>,
+    last_block: Option<char>,
+    block_lines: HashMap<char, u64>,
+    register_counts: BTreeMap<String, u64>,
+    /// Register, line number and count of each `9900` record.
+    declared_counts: BTreeMap<String, (usize, u64)>,
+    /// Line number and count of `9990`, checked with `9999` included.
+    declared_block_9: Option<(usize, u64)>,
+    total_lines: u64,
+    /// The first line was checked for the `0000` header, parsed or not.
+    header_checked: bool,
+    has_total: bool,
+}
+
+impl Default for SpedValidator {
+    fn default() -> Self {
+        SpedValidator::new()
+    }
+}
+
+impl SpedValidator {
+    /// Validator with the parent registers of EFD-Contribuições and EFD-ICMS-IPI.
+    pub fn new() -> Self {
+        let parents = PARENTS
+            .iter()
+            .map(|(child, parents)| {
+                let parents = parents.iter().map(|p| p.to_string()).collect();
+                (child.to_string(), parents)
+            })
+            .collect();
+
+        SpedValidator {
+            parents,
+            issues: Vec::new(),
+            stack: Vec::new(),
+            open_block: None,
+            last_block: None,
+            block_lines: HashMap::new(),
+            register_counts: BTreeMap::new(),
+            declared_counts: BTreeMap::new(),
+            declared_block_9: None,
+            total_lines: 0,
+            header_checked: false,
+            has_total: false,
+        }
+    }
+
+    /// Add or replace the possible parents of a register.
+    pub fn with_parent(mut self, register: &str, parents: &[&str]) -> Self {
+        let parents = parents.iter().map(|p| p.to_string()).collect();
+        self.parents.insert(register.to_string(), parents);
+        self
+    }
+
+    /// Read all records and return the issues found.
+    ///
+    /// Invalid lines become issues; only read failures are returned as errors.
+    pub fn validate<R: BufRead>(
+        mut self,
+        reader: &mut SpedReader<R>,
+    ) -> Result<Vec<SpedIssue>, SpedReadError> {
+        while let Some(result) = reader.next_record() {
+            match result {
+                Ok((_line_number, record)) => self.push(&record),
+                Err(SpedReadError::Record(error)) => self.push_error(error),
+                Err(error) => return Err(error),
+            }
+        }
+
+        Ok(self.finish())
+    }
+
+    /// Check the next record of the file.
+    pub fn push(&mut self, record: &SpedRecord) {
+        let line_number = record.line_number();
+        let register = record.register();
+        let block = register.chars().next().unwrap_or_default();
+
+        self.total_lines += 1;
+        *self.block_lines.entry(block).or_default() += 1;
+        *self
+            .register_counts
+            .entry(register.to_string())
+            .or_default() += 1;
+
+        if !self.header_checked {
+            self.header_checked = true;
+            if register != "0000" {
+                self.issue(line_number, SpedIssueKind::MissingHeader);
+            }
+        }
+
+        if register == "0000" {
+            return;
+        }
+
+        if register.ends_with("001") {
+            if let Some(open) = self.open_block {
+                self.issue(line_number, SpedIssueKind::BlockNotClosed { block: open });
+            }
+            self.open_block = Some(block);
+            self.stack = vec![register.to_string()];
+        } else if register == "9999" {
+            self.close_open_block(line_number);
+        } else if self.open_block != Some(block) {
+            if self.last_block != Some(block) {
+                self.close_open_block(line_number);
+                self.issue(line_number, SpedIssueKind::BlockNotOpened { block });
+            }
+            self.open_block = Some(block);
+        }
+
+        self.last_block = Some(block);
+
+        if register.ends_with("990") {
+            self.check_block_count(record, block);
+            self.open_block = None;
+            self.stack.clear();
+        } else if register == "9900" {
+            self.check_hierarchy(line_number, register);
+            self.read_register_count(record);
+        } else if register == "9999" {
+            self.read_total(record);
+        } else if !register.ends_with("001") {
+            self.check_hierarchy(line_number, register);
+        }
+    }
+
+    /// Record a line that could not be parsed.
+    ///
+    /// The line still counts in the `9999` total and in the `x990` count of the open block.
+    /// An invalid first line is not a `0000` header.
+    pub fn push_error(&mut self, error: SpedError) {
+        self.total_lines += 1;
+        if let Some(block) = self.open_block {
+            *self.block_lines.entry(block).or_default() += 1;
+        }
+
+        if !self.header_checked {
+            self.header_checked = true;
+            self.issue(error.line_number, SpedIssueKind::MissingHeader);
+        }
+
+        self.issues.push(error.into());
+    }
+
+    /// Check the totals and return all issues, sorted by line number.
+    pub fn finish(mut self) -> Vec<SpedIssue> {
+        if let Some(block) = self.open_block {
+            self.issues.push(SpedIssue {
+                line_number: None,
+                kind: SpedIssueKind::BlockNotClosed { block },
+            });
+        }
+
+        if let Some((line_number, found)) = self.declared_block_9 {
+            let expected = self.block_lines.get(&'9').copied().unwrap_or_default();
+            if expected != found {
+                self.issue(
+                    line_number,
+                    SpedIssueKind::WrongBlockCount {
+                        block: '9',
+                        expected,
+                        found,
+                    },
+                );
+            }
+        }
+
+        // Register counts are checked only when the file has the block 9.
+        let register_counts = match self.block_lines.contains_key(&'9') {
+            true => &self.register_counts,
+            false => &BTreeMap::new(),
+        };
+
+        for (register, &expected) in register_counts {
+            match self.declared_counts.get(register) {
+                Some(&(_line_number, found)) if found == expected => (),
+                Some(&(line_number, found)) => self.issues.push(SpedIssue {
+                    line_number: Some(line_number),
+                    kind: SpedIssueKind::WrongRegisterCount {
+                        register: register.clone(),
+                        expected,
+                        found,
+                    },
+                }),
+                None => self.issues.push(SpedIssue {
+                    line_number: None,
+                    kind: SpedIssueKind::MissingRegisterCount {
+                        register: register.clone(),
+                    },
+                }),
+            }
+        }
+
+        for (register, &(line_number, found)) in &self.declared_counts {
+            if found != 0 && !self.register_counts.contains_key(register) {
+                self.issues.push(SpedIssue {
+                    line_number: Some(line_number),
+                    kind: SpedIssueKind::WrongRegisterCount {
+                        register: register.clone(),
+                        expected: 0,
+                        found,
+                    },
+                });
+            }
+        }
+
+        if !self.has_total {
+            self.issues.push(SpedIssue {
+                line_number: None,
+                kind: SpedIssueKind::MissingTotal,
+            });
+        }
+
+        self.issues
+            .sort_by_key(|issue| issue.line_number.unwrap_or(usize::MAX));
+        self.issues
+    }
+
+    fn issue(&mut self, line_number: usize, kind: SpedIssueKind) {
+        self.issues.push(SpedIssue {
+            line_number: Some(line_number),
+            kind,
+        });
+    }
+
+    fn close_open_block(&mut self, line_number: usize) {
+        if let Some(block) = self.open_block.take() {
+            self.issue(line_number, SpedIssueKind::BlockNotClosed { block });
+        }
+    }
+
+    /// Find the parent in the open registers and make the record the last one.
+    fn check_hierarchy(&mut self, line_number: usize, register: &str) {
+        let position = match self.parents.get(register) {
+            Some(parents) => {
+                let position = self.stack.iter().rposition(|reg| parents.contains(reg));
+                if position.is_none() {
+                    let kind = SpedIssueKind::OrphanRecord {
+                        register: register.to_string(),
+                        parents: parents.clone(),
+                    };
+                    self.issue(line_number, kind);
+                }
+                position.map(|index| index + 1)
+            }
+            // Unknown registers: a repeated one replaces itself.
+            None => self.stack.iter().rposition(|reg| reg == register),
+        };
+
+        if let Some(position) = position {
+            self.stack.truncate(position);
+        }
+        self.stack.push(register.to_string());
+    }
+
+    fn check_block_count(&mut self, record: &SpedRecord, block: char) {
+        let found = match record.field_u64(2) {
+            Ok(Some(found)) => found,
+            Ok(None) => return,
+            Err(error) => return self.issues.push(error.into()),
+        };
+
+        if block == '9' {
+            self.declared_block_9 = Some((record.line_number(), found));
+            return;
+        }
+
+        let expected = self.block_lines.get(&block).copied().unwrap_or_default();
+        if expected != found {
+            let kind = SpedIssueKind::WrongBlockCount {
+                block,
+                expected,
+                found,
+            };
+            self.issue(record.line_number(), kind);
+        }
+    }
+
+    fn read_register_count(&mut self, record: &SpedRecord) {
+        let (register, found) = match (record.field_str(2), record.field_u64(3)) {
+            (Ok(register), Ok(Some(found))) => (register, found),
+            (Err(error), _) | (_, Err(error)) => return self.issues.push(error.into()),
+            (Ok(_), Ok(None)) => return,
+        };
+
+        let line_number = record.line_number();
+
+        if self.declared_counts.contains_key(register) {
+            let kind = SpedIssueKind::DuplicateRegisterCount {
+                register: register.to_string(),
+            };
+            self.issue(line_number, kind);
+        } else {
+            self.declared_counts
+                .insert(register.to_string(), (line_number, found));
+        }
+    }
+
+    fn read_total(&mut self, record: &SpedRecord) {
+        self.has_total = true;
+
+        match record.field_u64(2) {
+            Ok(Some(found)) if found != self.total_lines => {
+                let kind = SpedIssueKind::WrongTotal {
+                    expected: self.total_lines,
+                    found,
+                };
+                self.issue(record.line_number(), kind);
+            }
+            Ok(_) => (),
+            Err(error) => self.issues.push(error.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod sped_validator_tests {
+    use super::*;
+
+    // cargo test -- --show-output sped_validator_tests
+
+    fn validate(text: &str) -> Vec<SpedIssue> {
+        let mut reader = SpedReader::new(text.as_bytes());
+        SpedValidator::new().validate(&mut reader).unwrap()
+    }
+
+    const VALID: &str = "\
+|0000|006|
+|0001|0|
+|0140|1|
+|0150|P1|
+|0990|5|
+|C001|0|
+|C010|1|
+|C100|0|
+|C170|1|
+|C170|2|
+|C100|1|
+|C170|1|
+|C990|8|
+|9001|0|
+|9900|0000|1|
+|9900|0001|1|
+|9900|0140|1|
+|9900|0150|1|
+|9900|0990|1|
+|9900|C001|1|
+|9900|C010|1|
+|9900|C100|2|
+|9900|C170|3|
+|9900|C990|1|
+|9900|9001|1|
+|9900|9900|14|
+|9900|9990|1|
+|9900|9999|1|
+|9990|17|
+|9999|30|
+";
+
+    #[test]
+    fn valid_file() {
+        let issues = validate(VALID);
+        assert!(issues.is_empty(), "{issues:?}");
+    }
+
+    #[test]
+    fn wrong_counts() {
+        let text = VALID
+            .replace("|0990|5|", "|0990|6|")
+            .replace("|9900|C170|3|", "|9900|C170|2|")
+            .replace("|9900|C010|1|\n", "");
+
+        let kinds: Vec<SpedIssueKind> = validate(&text).into_iter().map(|i| i.kind).collect();
+
+        assert_eq!(
+            kinds,
+            [
+                SpedIssueKind::WrongBlockCount {
+                    block: '0',
+                    expected: 5,
+                    found: 6
+                },
+                SpedIssueKind::WrongRegisterCount {
+                    register: "C170".to_string(),
+                    expected: 3,
+                    found: 2
+                },
+                SpedIssueKind::WrongRegisterCount {
+                    register: "9900".to_string(),
+                    expected: 13,
+                    found: 14
+                },
+                SpedIssueKind::WrongBlockCount {
+                    block: '9',
+                    expected: 16,
+                    found: 17
+                },
+                SpedIssueKind::WrongTotal {
+                    expected: 29,
+                    found: 30
+                },
+                SpedIssueKind::MissingRegisterCount {
+                    register: "C010".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn wrong_structure() {
+        let text = "|0001|0|\n|C100|0|\n|C170|1|\n|X|\n|C990|4|\n|D100|0|\n";
+        let issues: Vec<String> = validate(text).iter().map(|i| i.to_string()).collect();
+
+        assert_eq!(
+            issues,
+            [
+                "line 1: the first record must be 0000",
+                "line 2: block 0 closed without 0990",
+                "line 2: block C opened without C001",
+                "line 2: register C100 must follow C010 or C001",
+                "line 4: invalid register code: \"X\"",
+                "line 6: block D opened without D001",
+                "line 6: register D100 must follow D010 or D001",
+                "block D closed without D990",
+                "the last record must be 9999",
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_lines() {
+        let text = VALID.replacen("|C170|2|", "|X|", 1);
+        let issues: Vec<String> = validate(&text).iter().map(|i| i.to_string()).collect();
+        assert_eq!(
+            issues,
+            [
+                "line 10: invalid register code: \"X\"",
+                "line 23: register C170 appears 2 times, but 9900 declares 3",
+            ]
+        );
+
+        let text = VALID.replacen("|0000|006|", "|X|", 1);
+        let issues: Vec<String> = validate(&text).iter().map(|i| i.to_string()).collect();
+        assert_eq!(
+            issues[..2],
+            [
+                "line 1: the first record must be 0000",
+                "line 1: invalid register code: \"X\"",
+            ]
+        );
+    }
+}
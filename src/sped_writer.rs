@@ -0,0 +1,399 @@
+use crate::{sped_reader::WINDOWS_1252, SpedEncoding};
+use std::{
+    collections::HashMap,
+    fmt,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+/// Errors returned by [`SpedWriter`].
+#[derive(Debug)]
+pub enum SpedWriteError {
+    /// Failure writing the file.
+    Io(io::Error),
+    /// The register code is not 4 ASCII alphanumeric characters.
+    InvalidRegister(String),
+    /// Registers `x990` and those of block 9 are generated by the writer.
+    ReservedRegister(String),
+    /// A record of a block that was already closed.
+    BlockAlreadyClosed(char),
+    /// A field contains the delimiter `|` or a line break.
+    InvalidField {
+        register: String,
+        field_index: usize,
+        value: String,
+    },
+    /// A field contains a character that Latin-1 (Windows-1252) cannot represent.
+    UnencodableCharacter {
+        register: String,
+        field_index: usize,
+        character: char,
+    },
+}
+
+impl fmt::Display for SpedWriteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpedWriteError::Io(error) => write!(f, "failed to write SPED file: {error}"),
+            SpedWriteError::InvalidRegister(reg) => write!(f, "invalid register code: {reg:?}"),
+            SpedWriteError::ReservedRegister(reg) => {
+                write!(f, "register {reg} is generated by the writer")
+            }
+            SpedWriteError::BlockAlreadyClosed(block) => {
+                write!(f, "block {block} was already closed")
+            }
+            SpedWriteError::InvalidField {
+                register,
+                field_index,
+                value,
+            } => write!(
+                f,
+                "register {register}, field {field_index:02}: '|' or line break in {value:?}"
+            ),
+            SpedWriteError::UnencodableCharacter {
+                register,
+                field_index,
+                character,
+            } => write!(
+                f,
+                "register {register}, field {field_index:02}: {character:?} is not Latin-1"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SpedWriteError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SpedWriteError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for SpedWriteError {
+    fn from(error: io::Error) -> Self {
+        SpedWriteError::Io(error)
+    }
+}
+
+/**
+Writer of SPED files that generates the closing and totalizer registers.
+
+Records are written in order, without the `x990` registers and the block 9:
+- a block is closed with `x990` when the next block starts;
+- [`SpedWriter::finish`] closes the last block and writes `9001`,
+  the `9900` register counts, `9990` and `9999`.
+
+Fields are validated, and the output is encoded in Latin-1 (Windows-1252),
+with CRLF line terminators. Amounts are formatted from [`Decimal`](crate::Decimal)
+with [`crate::decimal_comma`], so totals read with [`crate::SpedRecord::field_decimal`]
+are written back without `f64` rounding.
+
+Example:
+```
+    use claudiofsr_lib::{decimal_comma, Decimal, SpedEncoding, SpedWriter};
+
+    let mut writer = SpedWriter::new(Vec::new()).with_encoding(SpedEncoding::Utf8);
+
+    writer.write_record(&["0000", "006", "Ação"]).unwrap();
+    writer.write_record(&["0001", "0"]).unwrap();
+    writer.write_record(&["C001", "0"]).unwrap();
+    writer.write_record(&["C100", &decimal_comma(Decimal::new(12345, 1), 2)]).unwrap();
+
+    let bytes = writer.finish().unwrap();
+
+    let expected = "\
+|0000|006|Ação|\r
+|0001|0|\r
+|0990|3|\r
+|C001|0|\r
+|C100|1234,50|\r
+|C990|3|\r
+|9001|0|\r
+|9900|0000|1|\r
+|9900|0001|1|\r
+|9900|0990|1|\r
+|9900|C001|1|\r
+|9900|C100|1|\r
+|9900|C990|1|\r
+|9900|9001|1|\r
+|9900|9900|10|\r
+|9900|9990|1|\r
+|9900|9999|1|\r
+|9990|13|\r
+|9999|19|\r
+";
+
+    assert_eq!(String::from_utf8(bytes).unwrap(), expected);
+```
+*/
+pub struct SpedWriter<W: Write> {
+    writer: W,
+    encoding: SpedEncoding,
+    buffer: Vec<u8>,
+    /// Registers in order of first appearance, with their counts.
+    registers: Vec<(String, u64)>,
+    positions: HashMap<String, usize>,
+    /// Current block and its number of lines.
+    block: Option<(char, u64)>,
+    closed_blocks: Vec<char>,
+    total_lines: u64,
+}
+
+impl SpedWriter<BufWriter<File>> {
+    /// Create (or truncate) a SPED file.
+    pub fn create<P>(path: P) -> Result<Self, io::Error>
+    where
+        P: AsRef<Path>,
+    {
+        let file = File::create(path)?;
+        Ok(SpedWriter::new(BufWriter::new(file)))
+    }
+}
+
+impl<W: Write> SpedWriter<W> {
+    /// Write SPED records, encoded in Latin-1, to any writer.
+    pub fn new(writer: W) -> Self {
+        SpedWriter {
+            writer,
+            encoding: SpedEncoding::Latin1,
+            buffer: Vec::new(),
+            registers: Vec::new(),
+            positions: HashMap::new(),
+            block: None,
+            closed_blocks: Vec::new(),
+            total_lines: 0,
+        }
+    }
+
+    /// Write with another encoding instead of Latin-1.
+    pub fn with_encoding(mut self, encoding: SpedEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Number of lines written so far, including the generated `x990`.
+    pub fn line_count(&self) -> u64 {
+        self.total_lines
+    }
+
+    /// Write a record: the register code followed by its fields.
+    pub fn write_record<S: AsRef<str>>(&mut self, fields: &[S]) -> Result<(), SpedWriteError> {
+        let register = fields.first().map(|f| f.as_ref()).unwrap_or_default();
+
+        if register.len() != 4 || !register.bytes().all(|b| b.is_ascii_alphanumeric()) {
+            return Err(SpedWriteError::InvalidRegister(register.to_string()));
+        }
+
+        let block = register.chars().next().unwrap_or_default();
+
+        if register.ends_with("990") || block == '9' {
+            return Err(SpedWriteError::ReservedRegister(register.to_string()));
+        }
+
+        if self.closed_blocks.contains(&block) {
+            return Err(SpedWriteError::BlockAlreadyClosed(block));
+        }
+
+        // Validate before writing anything, so that a failed record leaves no trace.
+        self.encode(fields)?;
+
+        match self.block {
+            Some((current, _)) if current == block => (),
+            Some(_) => {
+                self.close_block()?;
+                self.block = Some((block, 0));
+                self.encode(fields)?;
+            }
+            None => self.block = Some((block, 0)),
+        }
+
+        self.write_buffer(register)
+    }
+
+    /// Close the last block, write the block 9 and return the inner writer.
+    pub fn finish(mut self) -> Result<W, SpedWriteError> {
+        if self.block.is_some() {
+            self.close_block()?;
+        }
+
+        self.block = Some(('9', 0));
+        self.write_generated(&["9001", "0"])?;
+
+        // 9900 records for the registers so far, for themselves, 9990 and 9999.
+        let count_9900 = self.registers.len() as u64 + 3;
+        let mut counts: Vec<(String, u64)> = self.registers.clone();
+        counts.push(("9900".to_string(), count_9900));
+        counts.push(("9990".to_string(), 1));
+        counts.push(("9999".to_string(), 1));
+
+        for (register, count) in counts {
+            self.write_generated(&["9900", &register, &count.to_string()])?;
+        }
+
+        // 9990 and 9999 are also lines of the block 9.
+        let lines_9 = self.block.map(|(_, lines)| lines).unwrap_or_default() + 2;
+        self.write_generated(&["9990", &lines_9.to_string()])?;
+
+        let total = self.total_lines + 1;
+        self.write_generated(&["9999", &total.to_string()])?;
+
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    /// Write `x990` with the number of lines of the current block.
+    fn close_block(&mut self) -> Result<(), SpedWriteError> {
+        if let Some((block, lines)) = self.block {
+            let register = format!("{block}990");
+            self.write_generated(&[register.as_str(), &(lines + 1).to_string()])?;
+            self.closed_blocks.push(block);
+        }
+        Ok(())
+    }
+
+    fn write_generated(&mut self, fields: &[&str]) -> Result<(), SpedWriteError> {
+        self.encode(fields)?;
+        self.write_buffer(fields[0])
+    }
+
+    /// Write the encoded line and update the counts.
+    fn write_buffer(&mut self, register: &str) -> Result<(), SpedWriteError> {
+        self.writer.write_all(&self.buffer)?;
+
+        self.total_lines += 1;
+        if let Some((_, lines)) = self.block.as_mut() {
+            *lines += 1;
+        }
+
+        match self.positions.get(register) {
+            Some(&index) => self.registers[index].1 += 1,
+            None => {
+                self.positions
+                    .insert(register.to_string(), self.registers.len());
+                self.registers.push((register.to_string(), 1));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Encode the line "|f0|f1|...|\r\n" into `self.buffer`.
+    fn encode<S: AsRef<str>>(&mut self, fields: &[S]) -> Result<(), SpedWriteError> {
+        let register = fields[0].as_ref();
+        self.buffer.clear();
+        self.buffer.push(b'|');
+
+        for (index, field) in fields.iter().enumerate() {
+            let value = field.as_ref();
+            let field_index = index + 1;
+
+            if value.contains(['|', '\r', '\n']) {
+                return Err(SpedWriteError::InvalidField {
+                    register: register.to_string(),
+                    field_index,
+                    value: value.to_string(),
+                });
+            }
+
+            match self.encoding {
+                SpedEncoding::Utf8 => self.buffer.extend_from_slice(value.as_bytes()),
+                SpedEncoding::Latin1 => {
+                    for character in value.chars() {
+                        let byte = encode_windows_1252(character).ok_or_else(|| {
+                            SpedWriteError::UnencodableCharacter {
+                                register: register.to_string(),
+                                field_index,
+                                character,
+                            }
+                        })?;
+                        self.buffer.push(byte);
+                    }
+                }
+            }
+
+            self.buffer.push(b'|');
+        }
+
+        self.buffer.extend_from_slice(b"\r\n");
+
+        Ok(())
+    }
+}
+
+/// Encode one character in Windows-1252.
+fn encode_windows_1252(character: char) -> Option<u8> {
+    match u32::from(character) {
+        code @ (0..=0x7F | 0xA0..=0xFF) => u8::try_from(code).ok(),
+        _ => WINDOWS_1252
+            .iter()
+            .position(|&c| c == character)
+            .and_then(|index| u8::try_from(0x80 + index).ok()),
+    }
+}
+
+#[cfg(test)]
+mod sped_writer_tests {
+    use super::*;
+    use crate::{SpedReader, SpedValidator};
+
+    // cargo test -- --show-output sped_writer_tests
+
+    #[test]
+    fn write_and_validate() {
+        let mut writer = SpedWriter::new(Vec::new());
+
+        writer.write_record(&["0000", "006", "Razão €"]).unwrap();
+        writer.write_record(&["0001", "0"]).unwrap();
+        writer.write_record(&["0140", "1"]).unwrap();
+        writer.write_record(&["0150", "P1"]).unwrap();
+        writer.write_record(&["C001", "0"]).unwrap();
+        writer.write_record(&["C010", "1"]).unwrap();
+        for _ in 0..3 {
+            writer.write_record(&["C100", "0"]).unwrap();
+            writer.write_record(&["C170", "1"]).unwrap();
+        }
+
+        let bytes = writer.finish().unwrap();
+        assert!(bytes.starts_with(b"|0000|006|Raz\xE3o \x80|\r\n"));
+
+        let mut reader = SpedReader::new(bytes.as_slice());
+        let issues = SpedValidator::new().validate(&mut reader).unwrap();
+        assert!(issues.is_empty(), "{issues:?}");
+    }
+
+    #[test]
+    fn invalid_records() {
+        let mut writer = SpedWriter::new(Vec::new());
+
+        assert!(matches!(
+            writer.write_record(&["0000", "a|b"]),
+            Err(SpedWriteError::InvalidField { field_index: 2, .. })
+        ));
+        assert!(matches!(
+            writer.write_record(&["0000", "✓"]),
+            Err(SpedWriteError::UnencodableCharacter {
+                character: '✓', ..
+            })
+        ));
+        assert!(matches!(
+            writer.write_record(&["C990", "1"]),
+            Err(SpedWriteError::ReservedRegister(_))
+        ));
+        assert!(matches!(
+            writer.write_record(&["C1"]),
+            Err(SpedWriteError::InvalidRegister(_))
+        ));
+        assert_eq!(writer.line_count(), 0);
+
+        writer.write_record(&["C001", "0"]).unwrap();
+        writer.write_record(&["D001", "0"]).unwrap();
+        assert!(matches!(
+            writer.write_record(&["C100", "0"]),
+            Err(SpedWriteError::BlockAlreadyClosed('C'))
+        ));
+        assert_eq!(writer.line_count(), 3);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::Cpf;
 use std::ops::Deref;
 
 /// Trait extension for String
@@ -176,6 +177,16 @@ pub trait StrExtension {
     */
     fn replace_multiple_whitespaces(&self) -> String;
 
+    /**
+    Replace accented Latin letters with the unaccented ones.
+    ```
+        use claudiofsr_lib::StrExtension;
+        let text: &str = "Ação, Pão e Café em SÃO PAULO";
+        assert_eq!(text.remove_accents(), "Acao, Pao e Cafe em SAO PAULO");
+    ```
+    */
+    fn remove_accents(&self) -> String;
+
     /**
     Remove all non-digits characters
 
@@ -296,6 +307,9 @@ pub trait StrExtension {
 
     /**
     Format CNPJ (ASCII alphanumeric with 14 characters)
+
+    Only inserts the punctuation, check digits are not verified.
+    To validate, see [`Cnpj`](crate::Cnpj).
     ```
         use claudiofsr_lib::StrExtension;
         let cnpj: &str = "12ABC678901234";
@@ -308,14 +322,19 @@ pub trait StrExtension {
     fn format_cnpj(&self) -> String;
 
     /**
-    Format CPF (ASCII alphanumeric with 11 characters)
+    Format CPF (11 digits with valid check digits)
+
+    Invalid CPFs are returned unchanged. See [`Cpf`](crate::Cpf).
     ```
         use claudiofsr_lib::StrExtension;
-        let cpf: &str = "123ABC78901";
+        let cpf: &str = "52998224725";
         assert_eq!(
             cpf.format_cpf(),
-            "123.ABC.789-01"
+            "529.982.247-25"
         );
+
+        let invalid: &str = "123ABC78901";
+        assert_eq!(invalid.format_cpf(), "123ABC78901");
     ```
     */
     fn format_cpf(&self) -> String;
@@ -389,6 +408,30 @@ where
         new_str
     }
 
+    fn remove_accents(&self) -> String {
+        self.chars()
+            .map(|ch| match ch {
+                'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => 'a',
+                'À' | 'Á' | 'Â' | 'Ã' | 'Ä' | 'Å' => 'A',
+                'è' | 'é' | 'ê' | 'ë' => 'e',
+                'È' | 'É' | 'Ê' | 'Ë' => 'E',
+                'ì' | 'í' | 'î' | 'ï' => 'i',
+                'Ì' | 'Í' | 'Î' | 'Ï' => 'I',
+                'ò' | 'ó' | 'ô' | 'õ' | 'ö' => 'o',
+                'Ò' | 'Ó' | 'Ô' | 'Õ' | 'Ö' => 'O',
+                'ù' | 'ú' | 'û' | 'ü' => 'u',
+                'Ù' | 'Ú' | 'Û' | 'Ü' => 'U',
+                'ý' | 'ÿ' => 'y',
+                'Ý' => 'Y',
+                'ç' => 'c',
+                'Ç' => 'C',
+                'ñ' => 'n',
+                'Ñ' => 'N',
+                _ => ch,
+            })
+            .collect()
+    }
+
     fn remove_non_digits(&self) -> String {
         self.chars().filter(|c| c.is_ascii_digit()).collect()
     }
@@ -482,20 +525,9 @@ where
     }
 
     fn format_cpf(&self) -> String {
-        if self.chars().count() == 11 && self.is_ascii_alphanumeric() {
-            let formated: String = [
-                &self[0..3],
-                ".",
-                &self[3..6],
-                ".",
-                &self[6..9],
-                "-",
-                &self[9..],
-            ]
-            .concat();
-            formated
-        } else {
-            self.to_string()
+        match Cpf::parse(self) {
+            Ok(cpf) => cpf.to_string(),
+            Err(_) => self.to_string(),
         }
     }
 
@@ -0,0 +1,299 @@
+use std::{fmt, str::FromStr};
+
+/// Errors returned when parsing a state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UfError {
+    /// Not an IBGE state code.
+    InvalidCodigo(u8),
+    /// Neither a state abbreviation nor an IBGE state code.
+    InvalidSigla(String),
+}
+
+impl fmt::Display for UfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UfError::InvalidCodigo(codigo) => write!(f, "invalid IBGE state code: {codigo}"),
+            UfError::InvalidSigla(sigla) => write!(f, "invalid state (UF): {sigla:?}"),
+        }
+    }
+}
+
+impl std::error::Error for UfError {}
+
+/// The five regions of Brazil.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Regiao {
+    Norte,
+    Nordeste,
+    Sudeste,
+    Sul,
+    CentroOeste,
+}
+
+impl Regiao {
+    pub const fn nome(self) -> &'static str {
+        match self {
+            Regiao::Norte => "Norte",
+            Regiao::Nordeste => "Nordeste",
+            Regiao::Sudeste => "Sudeste",
+            Regiao::Sul => "Sul",
+            Regiao::CentroOeste => "Centro-Oeste",
+        }
+    }
+}
+
+impl fmt::Display for Regiao {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.nome())
+    }
+}
+
+/**
+Brazilian state (Unidade da Federação), whose discriminant is the IBGE code.
+
+Example:
+```
+    use claudiofsr_lib::{Regiao, Uf};
+
+    let uf: Uf = "sp".parse().unwrap();
+
+    assert_eq!(uf, Uf::SP);
+    assert_eq!(uf.codigo(), 35);
+    assert_eq!(uf.nome(), "São Paulo");
+    assert_eq!(uf.regiao(), Regiao::Sudeste);
+    assert_eq!(uf.to_string(), "SP");
+
+    assert_eq!(Uf::try_from(53), Ok(Uf::DF));
+    assert_eq!("43".parse(), Ok(Uf::RS));
+    assert_eq!(Uf::ALL.len(), 27);
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Uf {
+    RO = 11,
+    AC = 12,
+    AM = 13,
+    RR = 14,
+    PA = 15,
+    AP = 16,
+    TO = 17,
+    MA = 21,
+    PI = 22,
+    CE = 23,
+    RN = 24,
+    PB = 25,
+    PE = 26,
+    AL = 27,
+    SE = 28,
+    BA = 29,
+    MG = 31,
+    ES = 32,
+    RJ = 33,
+    SP = 35,
+    PR = 41,
+    SC = 42,
+    RS = 43,
+    MS = 50,
+    MT = 51,
+    GO = 52,
+    DF = 53,
+}
+
+impl Uf {
+    /// The 27 states, ordered by IBGE code.
+    pub const ALL: [Uf; 27] = [
+        Uf::RO,
+        Uf::AC,
+        Uf::AM,
+        Uf::RR,
+        Uf::PA,
+        Uf::AP,
+        Uf::TO,
+        Uf::MA,
+        Uf::PI,
+        Uf::CE,
+        Uf::RN,
+        Uf::PB,
+        Uf::PE,
+        Uf::AL,
+        Uf::SE,
+        Uf::BA,
+        Uf::MG,
+        Uf::ES,
+        Uf::RJ,
+        Uf::SP,
+        Uf::PR,
+        Uf::SC,
+        Uf::RS,
+        Uf::MS,
+        Uf::MT,
+        Uf::GO,
+        Uf::DF,
+    ];
+
+    /// State with the IBGE code.
+    pub const fn from_codigo(codigo: u8) -> Option<Self> {
+        let mut index = 0;
+        while index < Uf::ALL.len() {
+            if Uf::ALL[index] as u8 == codigo {
+                return Some(Uf::ALL[index]);
+            }
+            index += 1;
+        }
+        None
+    }
+
+    /// State with the abbreviation, ignoring case.
+    pub fn from_sigla(sigla: &str) -> Option<Self> {
+        Uf::ALL
+            .into_iter()
+            .find(|uf| uf.sigla().eq_ignore_ascii_case(sigla.trim()))
+    }
+
+    /// IBGE code of the state (cUF).
+    pub const fn codigo(self) -> u8 {
+        self as u8
+    }
+
+    pub const fn sigla(self) -> &'static str {
+        match self {
+            Uf::RO => "RO",
+            Uf::AC => "AC",
+            Uf::AM => "AM",
+            Uf::RR => "RR",
+            Uf::PA => "PA",
+            Uf::AP => "AP",
+            Uf::TO => "TO",
+            Uf::MA => "MA",
+            Uf::PI => "PI",
+            Uf::CE => "CE",
+            Uf::RN => "RN",
+            Uf::PB => "PB",
+            Uf::PE => "PE",
+            Uf::AL => "AL",
+            Uf::SE => "SE",
+            Uf::BA => "BA",
+            Uf::MG => "MG",
+            Uf::ES => "ES",
+            Uf::RJ => "RJ",
+            Uf::SP => "SP",
+            Uf::PR => "PR",
+            Uf::SC => "SC",
+            Uf::RS => "RS",
+            Uf::MS => "MS",
+            Uf::MT => "MT",
+            Uf::GO => "GO",
+            Uf::DF => "DF",
+        }
+    }
+
+    pub const fn nome(self) -> &'static str {
+        match self {
+            Uf::RO => "Rondônia",
+            Uf::AC => "Acre",
+            Uf::AM => "Amazonas",
+            Uf::RR => "Roraima",
+            Uf::PA => "Pará",
+            Uf::AP => "Amapá",
+            Uf::TO => "Tocantins",
+            Uf::MA => "Maranhão",
+            Uf::PI => "Piauí",
+            Uf::CE => "Ceará",
+            Uf::RN => "Rio Grande do Norte",
+            Uf::PB => "Paraíba",
+            Uf::PE => "Pernambuco",
+            Uf::AL => "Alagoas",
+            Uf::SE => "Sergipe",
+            Uf::BA => "Bahia",
+            Uf::MG => "Minas Gerais",
+            Uf::ES => "Espírito Santo",
+            Uf::RJ => "Rio de Janeiro",
+            Uf::SP => "São Paulo",
+            Uf::PR => "Paraná",
+            Uf::SC => "Santa Catarina",
+            Uf::RS => "Rio Grande do Sul",
+            Uf::MS => "Mato Grosso do Sul",
+            Uf::MT => "Mato Grosso",
+            Uf::GO => "Goiás",
+            Uf::DF => "Distrito Federal",
+        }
+    }
+
+    /// Region given by the first digit of the IBGE code.
+    pub const fn regiao(self) -> Regiao {
+        match self.codigo() / 10 {
+            1 => Regiao::Norte,
+            2 => Regiao::Nordeste,
+            3 => Regiao::Sudeste,
+            4 => Regiao::Sul,
+            _ => Regiao::CentroOeste,
+        }
+    }
+}
+
+impl TryFrom<u8> for Uf {
+    type Error = UfError;
+
+    fn try_from(codigo: u8) -> Result<Self, Self::Error> {
+        Uf::from_codigo(codigo).ok_or(UfError::InvalidCodigo(codigo))
+    }
+}
+
+impl FromStr for Uf {
+    type Err = UfError;
+
+    /// Abbreviation ("SP", "sp") or IBGE code ("35").
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = s.trim();
+
+        let uf = match text.parse::<u8>() {
+            Ok(codigo) => Uf::from_codigo(codigo),
+            Err(_) => Uf::from_sigla(text),
+        };
+
+        uf.ok_or_else(|| UfError::InvalidSigla(text.to_string()))
+    }
+}
+
+impl fmt::Display for Uf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.sigla())
+    }
+}
+
+#[cfg(test)]
+mod uf_tests {
+    use super::*;
+
+    // cargo test -- --show-output uf_tests
+
+    #[test]
+    fn codes_and_abbreviations() {
+        for uf in Uf::ALL {
+            assert_eq!(Uf::from_codigo(uf.codigo()), Some(uf));
+            assert_eq!(uf.sigla().parse(), Ok(uf));
+            assert_eq!(uf.sigla().to_lowercase().parse(), Ok(uf));
+            assert_eq!(uf.codigo().to_string().parse(), Ok(uf));
+        }
+
+        assert!(Uf::ALL.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(Uf::try_from(20), Err(UfError::InvalidCodigo(20)));
+        assert_eq!(
+            "XX".parse::<Uf>(),
+            Err(UfError::InvalidSigla("XX".to_string()))
+        );
+        assert!("".parse::<Uf>().is_err());
+    }
+
+    #[test]
+    fn regions() {
+        let count = |regiao| Uf::ALL.iter().filter(|uf| uf.regiao() == regiao).count();
+
+        assert_eq!(count(Regiao::Norte), 7);
+        assert_eq!(count(Regiao::Nordeste), 9);
+        assert_eq!(count(Regiao::Sudeste), 4);
+        assert_eq!(count(Regiao::Sul), 3);
+        assert_eq!(count(Regiao::CentroOeste), 4);
+        assert_eq!(Uf::DF.regiao().to_string(), "Centro-Oeste");
+    }
+}